@@ -8,6 +8,7 @@ pub mod combat_classes;
 pub mod animations;
 pub mod materials;
 pub mod rarity;
+pub mod weapons;
 
 pub const SIZE_BLOCK: i64 = 0x_01_00_00;
 pub const SIZE_MAPBLOCK: i64 = SIZE_BLOCK * 8;
@@ -44,6 +45,10 @@ pub const PLAYABLE_RACES: [Race; 16] = [
 	UndeadFemale
 ];
 
+pub const MAX_SPIRITS_TWO_HANDED: i32 = 32;
+///spirits sit on a voxel of the item's model, none of which is more than 64 voxels across
+pub const MAX_SPIRIT_OFFSET: i8 = 64;
+
 pub const TWO_HANDED_WEAPONS: [Weapon; 10] = [
 	Longsword,
 	Bow,
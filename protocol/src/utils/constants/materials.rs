@@ -1,5 +1,7 @@
+use crate::packet::common::item::kind::Weapon;
 use crate::packet::common::item::Material;
 use crate::packet::common::item::Material::*;
+use crate::packet::creature_update::Occupation;
 
 pub const ACCESSORIES: [Material; 2] = [
 	Gold,
@@ -14,6 +16,54 @@ pub const LAMP: [Material; 1] = [
 	Iron
 ];
 
+pub const SPIRITS: [Material; 4] = [
+	Fire,
+	Unholy,
+	IceSpirit,
+	Wind
+];
+
+
+
+pub const ARMOR_WARRIOR: [Material; 7] = [
+	Bone,
+	Mammoth,
+	Gold,
+	Iron,
+	Obsidian,
+	Saurian,
+	Ice
+];
+
+pub const ARMOR_RANGER: [Material; 5] = [
+	Bone,
+	Mammoth,
+	Gold,
+	Parrot,
+	Linen
+];
+
+pub const ARMOR_MAGE: [Material; 5] = [
+	Bone,
+	Mammoth,
+	Gold,
+	Licht,
+	Silk
+];
+
+pub const ARMOR_ROGUE: [Material; 4] = [
+	Bone,
+	Mammoth,
+	Gold,
+	Cotton
+];
+
+pub const ARMOR_OTHER: [Material; 3] = [
+	Bone,
+	Mammoth,
+	Gold
+];
+
 
 
 pub const SWORD: [Material; 3] = [
@@ -111,4 +161,36 @@ pub const PICKAXE: [Material; 3] = [
 pub const TORCH: [Material; 2] = [
 	None,
 	Wood
-];
\ No newline at end of file
+];
+
+///armor materials wearable by each occupation, everyone else is limited to `ARMOR_OTHER`
+pub const ARMOR_BY_OCCUPATION: [(Occupation, &[Material]); 4] = [
+	(Occupation::Warrior, &ARMOR_WARRIOR),
+	(Occupation::Ranger , &ARMOR_RANGER),
+	(Occupation::Mage   , &ARMOR_MAGE),
+	(Occupation::Rogue  , &ARMOR_ROGUE)
+];
+
+pub const BY_WEAPON: [(Weapon, &[Material]); 21] = [
+	(Weapon::Sword     , &SWORD),
+	(Weapon::Axe       , &AXE),
+	(Weapon::Mace      , &MACE),
+	(Weapon::Dagger    , &DAGGER),
+	(Weapon::Fist      , &FIST),
+	(Weapon::Longsword , &LONGSWORD),
+	(Weapon::Bow       , &BOW),
+	(Weapon::Crossbow  , &CROSSBOW),
+	(Weapon::Boomerang , &BOOMERANG),
+	(Weapon::Arrow     , &ARROW),
+	(Weapon::Staff     , &STAFF),
+	(Weapon::Wand      , &WAND),
+	(Weapon::Bracelet  , &BRACELET),
+	(Weapon::Shield    , &SHIELD),
+	(Weapon::Quiver    , &QUIVER),
+	(Weapon::Greatsword, &GREATSWORD),
+	(Weapon::Greataxe  , &GREATAXE),
+	(Weapon::Greatmace , &GREATMACE),
+	(Weapon::Pitchfork , &PITCHFORK),
+	(Weapon::Pickaxe   , &PICKAXE),
+	(Weapon::Torch     , &TORCH)
+];
@@ -0,0 +1,46 @@
+use crate::packet::common::item::kind::Weapon;
+use crate::packet::common::item::kind::Weapon::*;
+use crate::packet::creature_update::Occupation;
+use crate::packet::creature_update::Occupation::{Mage, Ranger, Rogue, Warrior};
+
+pub const WARRIOR: [Weapon; 7] = [
+	Sword,
+	Axe,
+	Mace,
+	Shield,
+	Greatsword,
+	Greataxe,
+	Greatmace
+];
+
+pub const RANGER: [Weapon; 3] = [
+	Bow,
+	Crossbow,
+	Boomerang
+];
+
+pub const MAGE: [Weapon; 3] = [
+	Staff,
+	Wand,
+	Bracelet
+];
+
+pub const ROGUE: [Weapon; 3] = [
+	Dagger,
+	Fist,
+	Longsword
+];
+
+///wearable regardless of occupation
+pub const TOOLS: [Weapon; 3] = [
+	Pitchfork,
+	Pickaxe,
+	Torch
+];
+
+pub const BY_OCCUPATION: [(Occupation, &[Weapon]); 4] = [
+	(Warrior, &WARRIOR),
+	(Ranger , &RANGER),
+	(Mage   , &MAGE),
+	(Rogue  , &ROGUE)
+];
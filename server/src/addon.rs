@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use colour::red_ln;
 use futures::future::join_all;
 use tap::Pipe;
 use tokio::time::sleep;
@@ -8,9 +9,16 @@ use protocol::packet::{CreatureUpdate, IngameDatetime, WorldUpdate};
 use protocol::packet::world_update::{Sound, sound};
 use protocol::utils::sound_position_of;
 
+use crate::addon::anti_cheat::enforcement::Enforcement;
 use crate::addon::balancing::AirTimeTracker;
 use crate::addon::command_manager::CommandManager;
 use crate::addon::discord_integration::DiscordIntegration;
+use crate::addon::damage_over_time::PoisonTracker;
+use crate::addon::pvp::duels::DuelManager;
+use crate::addon::pvp::matches::MatchManager;
+use crate::addon::pvp::ratings::Ratings;
+use crate::addon::pvp::safe_zones::Regions;
+use crate::addon::pvp::tournaments::TournamentManager;
 use crate::server::creature::Creature;
 use crate::server::player::Player;
 use crate::server::Server;
@@ -21,12 +29,39 @@ pub mod balancing;
 pub mod discord_integration;
 pub mod command_manager;
 pub mod pvp;
+pub mod damage_over_time;
+pub mod afk_detector;
+pub mod spectator;
 
 #[derive(Default)]
 pub struct Addons {
 	pub discord_integration: DiscordIntegration,
 	pub air_time_tracker: AirTimeTracker,
-	pub command_manager: CommandManager
+	pub command_manager: CommandManager,
+	pub poison_tracker: PoisonTracker,
+	pub anti_cheat: Enforcement,
+	pub matches: MatchManager,
+	pub duels: DuelManager,
+	pub ratings: Ratings,
+	pub tournaments: TournamentManager,
+	pub safe_zones: Regions
+}
+
+impl Addons {
+	///each addon ticks in its own task, so a panic in one of them doesn't hold up the others
+	pub async fn on_tick(&'static self, server: &'static Server) {
+		let results = join_all([
+			tokio::spawn(self.poison_tracker.on_tick(server)),
+			tokio::spawn(afk_detector::on_tick(server)),
+			tokio::spawn(self.matches.on_tick(server)),
+			tokio::spawn(self.duels.on_tick(server)),
+			tokio::spawn(spectator::on_tick(server))
+		]).await;
+
+		for error in results.into_iter().filter_map(Result::err) {
+			red_ln!("an addon failed to tick - {}", error);
+		}
+	}
 }
 
 pub fn fix_cutoff_animations(creature_update: &mut CreatureUpdate, previous_state: &Creature) {
@@ -64,5 +99,5 @@ pub async fn play_sound_at_player(player: &Player, kind: sound::Kind, pitch: f32
 		pitch,
 		volume,
 	};
-	player.send_ignoring(&WorldUpdate::from(sound)).await;
+	player.enqueue(&WorldUpdate::from(sound)).await;
 }
\ No newline at end of file
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use protocol::nalgebra::Point3;
+use protocol::packet::CreatureUpdate;
+use protocol::utils::constants::SIZE_BLOCK;
+
+use crate::server::player::Player;
+use crate::server::Server;
+
+#[derive(Debug, Default)]
+pub struct PlayerData {
+	///when and where the player was last seen doing something
+	last_activity: Option<(Instant, Point3<i64>)>,
+	warned: bool
+}
+
+#[expect(clippy::significant_drop_tightening, reason = "false positive")]
+pub async fn on_creature_update(source: &Player, packet: &CreatureUpdate) {
+	let character = source.character.read().await;
+	let new_animation_started = packet.animation_time.is_some_and(|animation_time| animation_time < character.animation_time);
+	let position = packet.position.unwrap_or(character.position);
+	drop(character);
+
+	let mut addon_data = source.addon_data.write().await;
+	let afk_data = &mut addon_data.afk_data;
+
+	//comparing against where the player was last active rather than the previous position, as physics cause some drift even when standing still
+	let moved = afk_data.last_activity.is_some_and(|(_, anchor)| (position - anchor).abs().max() > SIZE_BLOCK);
+
+	if afk_data.last_activity.is_none() || moved || new_animation_started {
+		afk_data.last_activity = Some((Instant::now(), position));
+		afk_data.warned = false;
+	}
+}
+
+pub async fn on_tick(server: &Server) {
+	for player in server.players.read().await.iter() {
+		let mut addon_data = player.addon_data.write().await;
+		let afk_data = &mut addon_data.afk_data;
+
+		let Some((timestamp, _)) = afk_data.last_activity else { continue; };
+		let idle_time = timestamp.elapsed();
+
+		if idle_time > server.config.afk_kick {
+			*afk_data = PlayerData::default(); //prevents kicking again every tick until the connection is closed
+			drop(addon_data);
+
+			let server_static = server.extend_lifetime();
+			let player = Arc::clone(player);
+			tokio::spawn(async move {
+				server_static.kick(&player, "afk").await;
+			});
+			continue;
+		}
+
+		if idle_time > server.config.afk_warning && !afk_data.warned {
+			afk_data.warned = true;
+			drop(addon_data);
+
+			player.notify(format!("you will be kicked in {} seconds unless you move", server.config.afk_kick.saturating_sub(idle_time).as_secs())).await;
+		}
+	}
+}
@@ -1,87 +1,184 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::RangeBounds;
 use std::result;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use boolinator::Boolinator;
 use tap::Tap;
 
-use protocol::packet::CreatureUpdate;
+use protocol::nalgebra::Point3;
+use protocol::packet::common::{CreatureId, Item};
+use protocol::packet::{CreatureUpdate, StatusEffect};
+use protocol::packet::status_effect::Kind as StatusEffectKind;
 
+use crate::server::creature::Creature;
 use crate::server::player::Player;
 
 use self::creature_update::*;
+use self::inventory::Ledger;
+use self::violation::{Check, Failure, Flag, Violation};
+use self::violation::Check::*;
 
 pub mod creature_update;
+pub mod hit;
+pub mod inventory;
+pub mod naming;
+pub mod projectile;
+pub mod status_effect;
+pub mod violation;
+pub mod enforcement;
+pub mod fingerprint;
+pub mod audit_log;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+///how much the score of each check decreases over time
+const SCORE_DECAY_PER_SECOND: f32 = 0.05;
+///how long the echo of a server-applied status effect may take to arrive after it ran out
+const ECHO_TOLERANCE: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Default)]
 pub struct PlayerData {
 	last_combo_update: Option<Instant>,
 	last_lag_spike: Option<Instant>,
-	shift_nanos: i64
+	shift_nanos: i64,
+	last_position_update: Option<Instant>,
+	movement_debt: i64,
+	airborne_gain: i64,
+	authorized_teleport: Option<(Point3<i64>, Instant)>,
+	///how many more hits each target can take right now, and when it was last hit
+	hit_budgets: HashMap<CreatureId, (f32, Instant)>,
+	///experience the server handed out which the client has yet to claim
+	granted_experience: i64,
+	inventory: Ledger,
+	///status effects the server applied to this player, and when the client stops echoing them
+	server_applied_effects: HashMap<StatusEffectKind, Instant>,
+	violation_scores: HashMap<Check, (f32, Instant)>
+}
+
+impl PlayerData {
+	///exempts the next jump to `destination` from movement checks
+	pub fn authorize_teleport(&mut self, destination: Point3<i64>) {
+		self.authorized_teleport = Some((destination, Instant::now()));
+	}
+
+	///exempts the client's echo of `status_effect` from inspection
+	pub fn expect_echo(&mut self, status_effect: &StatusEffect) {
+		let duration = Duration::from_millis(status_effect.duration.max(0).unsigned_abs().into());
+		self.server_applied_effects.insert(status_effect.kind, Instant::now() + duration + ECHO_TOLERANCE);
+	}
+
+	pub fn grant_experience(&mut self, experience: i32) {
+		self.granted_experience += i64::from(experience);
+	}
+
+	pub fn receive_item(&mut self, item: Item) {
+		self.inventory.receive(item);
+	}
+
+	pub fn on_join(&mut self, character: &Creature) {
+		self.inventory.seed(character);
+	}
+
+	///returns the updated score of the violated check
+	pub fn add_violation(&mut self, check: Check, severity: f32) -> f32 {
+		let (score, last_update) = self.violation_scores
+			.entry(check)
+			.or_insert((0.0, Instant::now()));
+
+		let decay = last_update.elapsed().as_secs_f32() * SCORE_DECAY_PER_SECOND;
+		*score = (*score - decay).max(0.0) + severity;
+		*last_update = Instant::now();
+
+		*score
+	}
 }
 
 #[expect(clippy::significant_drop_tightening, reason = "cannot drop any earlier")]
-pub async fn inspect_creature_update(source: &Player, packet: &CreatureUpdate) -> Result {
+pub async fn inspect_creature_update(source: &Player, packet: &CreatureUpdate) -> result::Result<(), Violation> {
 	let previous_state = source.character.read().await;
 	let updated_state = previous_state.clone().tap_mut(|state| state.update(packet));
 
-	packet.id.ensure_exact(&source.id, "creature_id")?;
+	if let Err(violation) = inspect_properties(source, packet, &previous_state, &updated_state).await {
+		return Err(Violation {
+			states: Some(Box::new((previous_state.clone(), updated_state))),
+			..violation
+		});
+	}
+
+	claim_experience(&previous_state, &updated_state, source).await;
+	Ok(())
+}
+
+///validates the absolute progression of a character that just joined, as there is no previous state to compare against
+pub fn inspect_join(character: &Creature, max_level: i32) -> result::Result<(), Violation> {
+	inspect_level(character, character).flag(Character)?;
+	character.level.ensure_at_most(max_level, "level.on_join").flag(Character)?;
+	inspect_experience(character, character).flag(Character)?;
+	inspect_skill_tree(character, character).flag(Character)?;
+	inspect_skill_caps(character, character).flag(SkillCap)
+}
+
+async fn inspect_properties(source: &Player, packet: &CreatureUpdate, previous_state: &Creature, updated_state: &Creature) -> result::Result<(), Violation> {
+	packet.id.ensure_exact(&source.id, "creature_id").flag(Character)?;
 
 	//todo: macro
-	if packet.position         .is_some() { inspect_position         (&previous_state, &updated_state)? };
-	if packet.rotation         .is_some() { inspect_rotation         (&previous_state, &updated_state)? };
-	if packet.velocity         .is_some() { inspect_velocity         (&previous_state, &updated_state)? };
-	if packet.acceleration     .is_some() { inspect_acceleration     (&previous_state, &updated_state)? };
-	if packet.velocity_extra   .is_some() { inspect_velocity_extra   (&previous_state, &updated_state)? };
-	if packet.head_tilt        .is_some() { inspect_head_tilt        (&previous_state, &updated_state)? };
-	if packet.flags_physics    .is_some() { inspect_flags_physics    (&previous_state, &updated_state)? };
-	if packet.affiliation      .is_some() { inspect_affiliation      (&previous_state, &updated_state)? };
-	if packet.race             .is_some() { inspect_race             (&previous_state, &updated_state)? };
-	if packet.animation        .is_some() { inspect_animation        (&previous_state, &updated_state)? };
-	if packet.animation_time   .is_some() { inspect_animation_time   (&previous_state, &updated_state)? };
-	if packet.combo            .is_some() { inspect_combo            (&previous_state, &updated_state)? };
-	if packet.combo_timeout    .is_some() { inspect_combo_timeout    (&previous_state, &updated_state, source).await? }; //todo: consistency
-	if packet.appearance       .is_some() { inspect_appearance       (&previous_state, &updated_state)? };
-	if packet.flags            .is_some() { inspect_flags            (&previous_state, &updated_state)? };
-	if packet.effect_time_dodge.is_some() { inspect_effect_time_dodge(&previous_state, &updated_state)? };
-	if packet.effect_time_stun .is_some() { inspect_effect_time_stun (&previous_state, &updated_state)? };
-	if packet.effect_time_fear .is_some() { inspect_effect_time_fear (&previous_state, &updated_state)? };
-	if packet.effect_time_chill.is_some() { inspect_effect_time_chill(&previous_state, &updated_state)? };
-	if packet.effect_time_wind .is_some() { inspect_effect_time_wind (&previous_state, &updated_state)? };
-	if packet.show_patch_time  .is_some() { inspect_show_patch_time  (&previous_state, &updated_state)? };
-	if packet.occupation       .is_some() { inspect_occupation       (&previous_state, &updated_state)? };
-	if packet.specialization   .is_some() { inspect_specialization   (&previous_state, &updated_state)? };
-	if packet.mana_charge      .is_some() { inspect_mana_charge      (&previous_state, &updated_state)? };
-	if packet.unknown24        .is_some() { inspect_unknown24        (&previous_state, &updated_state)? };
-	if packet.unknown25        .is_some() { inspect_unknown25        (&previous_state, &updated_state)? };
-	if packet.aim_offset       .is_some() { inspect_aim_offset       (&previous_state, &updated_state)? };
-	if packet.health           .is_some() { inspect_health           (&previous_state, &updated_state)? };
-	if packet.mana             .is_some() { inspect_mana             (&previous_state, &updated_state)? };
-	if packet.blocking_gauge   .is_some() { inspect_blocking_gauge   (&previous_state, &updated_state)? };
-	if packet.multipliers      .is_some() { inspect_multipliers      (&previous_state, &updated_state)? };
-	if packet.unknown31        .is_some() { inspect_unknown31        (&previous_state, &updated_state)? };
-	if packet.unknown32        .is_some() { inspect_unknown32        (&previous_state, &updated_state)? };
-	if packet.level            .is_some() { inspect_level            (&previous_state, &updated_state)? };
-	if packet.experience       .is_some() { inspect_experience       (&previous_state, &updated_state)? };
-	if packet.master           .is_some() { inspect_master           (&previous_state, &updated_state)? };
-	if packet.unknown36        .is_some() { inspect_unknown36        (&previous_state, &updated_state)? };
-	if packet.rarity           .is_some() { inspect_rarity           (&previous_state, &updated_state)? };
-	if packet.unknown38        .is_some() { inspect_unknown38        (&previous_state, &updated_state)? };
-	if packet.home_zone        .is_some() { inspect_home_zone        (&previous_state, &updated_state)? };
-	if packet.home             .is_some() { inspect_home             (&previous_state, &updated_state)? };
-	if packet.zone_to_reveal   .is_some() { inspect_zone_to_reveal   (&previous_state, &updated_state)? };
-	if packet.unknown42        .is_some() { inspect_unknown42        (&previous_state, &updated_state)? };
-	if packet.consumable       .is_some() { inspect_consumable       (&previous_state, &updated_state)? };
-	if packet.equipment        .is_some() { inspect_equipment        (&previous_state, &updated_state)? };
-	if packet.name             .is_some() { inspect_name             (&previous_state, &updated_state)? };
-	if packet.skill_tree       .is_some() { inspect_skill_tree       (&previous_state, &updated_state)? };
-	if packet.mana_cubes       .is_some() { inspect_mana_cubes       (&previous_state, &updated_state)? };
+	if packet.position         .is_some() { inspect_position         (previous_state, updated_state, source).await.flag(Movement)? }; //todo: consistency
+	if packet.rotation         .is_some() { inspect_rotation         (previous_state, updated_state).flag(Movement)? };
+	if packet.velocity         .is_some() { inspect_velocity         (previous_state, updated_state).flag(Movement)? };
+	if packet.acceleration     .is_some() { inspect_acceleration     (previous_state, updated_state).flag(Movement)? };
+	if packet.velocity_extra   .is_some() { inspect_velocity_extra   (previous_state, updated_state).flag(Movement)? };
+	if packet.head_tilt        .is_some() { inspect_head_tilt        (previous_state, updated_state).flag(Movement)? };
+	if packet.flags_physics    .is_some() { inspect_flags_physics    (previous_state, updated_state).flag(Movement)? };
+	if packet.affiliation      .is_some() { inspect_affiliation      (previous_state, updated_state).flag(Character)? };
+	if packet.race             .is_some() { inspect_race             (previous_state, updated_state).flag(Character)? };
+	if packet.animation        .is_some() { inspect_animation        (previous_state, updated_state).flag(Combat)? };
+	if packet.animation_time   .is_some() { inspect_animation_time   (previous_state, updated_state).flag(Combat)? };
+	if packet.combo            .is_some() { inspect_combo            (previous_state, updated_state).flag(Combat)? };
+	if packet.combo_timeout    .is_some() { inspect_combo_timeout    (previous_state, updated_state, source).await.flag(Timewarp)? }; //todo: consistency
+	if packet.appearance       .is_some() { inspect_appearance       (previous_state, updated_state).flag(Character)? };
+	if packet.flags            .is_some() { inspect_flags            (previous_state, updated_state).flag(Movement)? };
+	if packet.effect_time_dodge.is_some() { inspect_effect_time_dodge(previous_state, updated_state).flag(Combat)? };
+	if packet.effect_time_stun .is_some() { inspect_effect_time_stun (previous_state, updated_state).flag(Combat)? };
+	if packet.effect_time_fear .is_some() { inspect_effect_time_fear (previous_state, updated_state).flag(Combat)? };
+	if packet.effect_time_chill.is_some() { inspect_effect_time_chill(previous_state, updated_state).flag(Combat)? };
+	if packet.effect_time_wind .is_some() { inspect_effect_time_wind (previous_state, updated_state).flag(Combat)? };
+	if packet.show_patch_time  .is_some() { inspect_show_patch_time  (previous_state, updated_state).flag(Character)? };
+	if packet.occupation       .is_some() { inspect_occupation       (previous_state, updated_state).flag(Character)? };
+	if packet.specialization   .is_some() { inspect_specialization   (previous_state, updated_state).flag(Character)? };
+	if packet.mana_charge      .is_some() { inspect_mana_charge      (previous_state, updated_state).flag(Combat)? };
+	if packet.unknown24        .is_some() { inspect_unknown24        (previous_state, updated_state).flag(Character)? };
+	if packet.unknown25        .is_some() { inspect_unknown25        (previous_state, updated_state).flag(Character)? };
+	if packet.aim_offset       .is_some() { inspect_aim_offset       (previous_state, updated_state).flag(Combat)? };
+	if packet.health           .is_some() { inspect_health           (previous_state, updated_state).flag(Combat)? };
+	if packet.mana             .is_some() { inspect_mana             (previous_state, updated_state).flag(Combat)? };
+	if packet.blocking_gauge   .is_some() { inspect_blocking_gauge   (previous_state, updated_state).flag(Combat)? };
+	if packet.multipliers      .is_some() { inspect_multipliers      (previous_state, updated_state).flag(Character)? };
+	if packet.unknown31        .is_some() { inspect_unknown31        (previous_state, updated_state).flag(Character)? };
+	if packet.unknown32        .is_some() { inspect_unknown32        (previous_state, updated_state).flag(Character)? };
+	if packet.level            .is_some() { inspect_level            (previous_state, updated_state).flag(Character)? };
+	if packet.experience       .is_some() { inspect_experience       (previous_state, updated_state).flag(Character)? };
+	if packet.level.is_some() || packet.experience.is_some() { inspect_progression(previous_state, updated_state, source).await.flag(Character)? };
+	if packet.master           .is_some() { inspect_master           (previous_state, updated_state).flag(Character)? };
+	if packet.unknown36        .is_some() { inspect_unknown36        (previous_state, updated_state).flag(Character)? };
+	if packet.rarity           .is_some() { inspect_rarity           (previous_state, updated_state).flag(Character)? };
+	if packet.unknown38        .is_some() { inspect_unknown38        (previous_state, updated_state).flag(Character)? };
+	if packet.home_zone        .is_some() { inspect_home_zone        (previous_state, updated_state).flag(Character)? };
+	if packet.home             .is_some() { inspect_home             (previous_state, updated_state).flag(Character)? };
+	if packet.zone_to_reveal   .is_some() { inspect_zone_to_reveal   (previous_state, updated_state).flag(Character)? };
+	if packet.unknown42        .is_some() { inspect_unknown42        (previous_state, updated_state).flag(Character)? };
+	if packet.consumable       .is_some() { inspect_consumable       (previous_state, updated_state).flag(Character)? };
+	if packet.equipment        .is_some() { inspect_equipment        (previous_state, updated_state).flag(Character)? };
+	if packet.name             .is_some() { inspect_name             (previous_state, updated_state).flag(Character)? };
+	if packet.skill_tree       .is_some() { inspect_skill_tree       (previous_state, updated_state).flag(Character)? };
+	if packet.mana_cubes       .is_some() { inspect_mana_cubes       (previous_state, updated_state).flag(Character)? };
+	//last, so that a violation which is only logged can't skip any of the checks above
+	if packet.acceleration     .is_some() { inspect_acceleration_horizontal(previous_state, updated_state).flag(Acceleration)? };
+	if packet.skill_tree       .is_some() { inspect_skill_caps       (previous_state, updated_state).flag(SkillCap)? };
 
 	Ok(())
 }
 
-type Result = result::Result<(), String>;
+type Result = result::Result<(), Failure>;
 
 trait Ensure {
 	fn ensure(
@@ -100,7 +197,11 @@ impl Ensure for bool {
 		words: &str,
 		allowed: &(impl Debug + ?Sized)
 	) -> Result {
-		self.ok_or(format!("{property_name} was {actual:?}, allowed was {words} {allowed:?}"))
+		self.ok_or_else(|| Failure {
+			property: property_name.to_owned(),
+			actual: format!("{actual:?}"),
+			allowed: format!("{words} {allowed:?}")
+		})
 	}
 }
 
@@ -0,0 +1,167 @@
+use std::collections::{HashMap, VecDeque};
+use std::{fs, io};
+use std::io::ErrorKind::NotFound;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use colour::red_ln;
+use tokio::fs as tokio_fs;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::RwLock;
+
+use crate::addon::anti_cheat::violation::{Action, Check, Failure, Violation};
+use crate::server::utils::{escape_field, split_fields};
+
+///how many violations are kept in memory for `/ac <player>` and `/ac top`
+const HISTORY_SIZE: usize = 1000;
+///every line carries two full character dumps, so the log gets rotated once it reaches this size (in bytes)
+const MAX_FILE_SIZE: u64 = 16 * 1024 * 1024;
+
+///permanent record of every violation, including those of shadowed checks
+#[derive(Debug)]
+pub struct AuditLog {
+	history: RwLock<VecDeque<Entry>>
+}
+
+#[derive(Debug)]
+pub struct Entry {
+	pub timestamp: SystemTime,
+	pub player: String,
+	pub check: Check,
+	pub severity: f32,
+	pub failure: Failure
+}
+
+impl Default for AuditLog {
+	///picks up where the previous session left off
+	fn default() -> Self {
+		let file_content = [Self::ROTATED_FILE_PATH, Self::FILE_PATH]
+			.map(|file_path| match fs::read_to_string(file_path) {
+				Ok(content) => content,
+				Err(error) if error.kind() == NotFound => String::new(),
+				Err(error) => panic!("failed to load {file_path} - {error}")
+			})
+			.concat();
+
+		let mut history = file_content
+			.lines()
+			.rev()
+			.filter_map(Entry::parse) //skips lines that predate the current format
+			.take(HISTORY_SIZE)
+			.collect::<VecDeque<_>>();
+		history.make_contiguous().reverse();
+
+		Self {
+			history: RwLock::new(history)
+		}
+	}
+}
+
+impl Entry {
+	///the inverse of the line written by [`AuditLog::record`], minus the states
+	fn parse(line: &str) -> Option<Self> {
+		let fields = split_fields(line);
+		let [unix_time, player, check, severity, _score, _action, property, actual, allowed, ..] = fields.as_slice()
+			else { return None; };
+
+		Some(Self {
+			timestamp: UNIX_EPOCH + Duration::from_secs(unix_time.parse().ok()?),
+			player: player.clone(),
+			check: Check::from_name(check)?,
+			severity: severity.parse().ok()?,
+			failure: Failure {
+				property: property.clone(),
+				actual: actual.clone(),
+				allowed: allowed.clone()
+			}
+		})
+	}
+}
+
+impl AuditLog {
+	const FILE_PATH: &'static str = "anti_cheat.log";
+	///the previous log, overwritten on each rotation
+	const ROTATED_FILE_PATH: &'static str = "anti_cheat.log.old";
+
+	pub async fn record(&self, player: &str, violation: &Violation, severity: f32, score: f32, action: Action) {
+		let unix_time = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_secs();
+		let (previous_state, updated_state) = violation.states
+			.as_deref()
+			.map_or((String::new(), String::new()), |(previous, updated)| (format!("{previous:?}"), format!("{updated:?}")));
+		let Failure { ref property, ref actual, ref allowed } = violation.failure;
+		let fields = [
+			unix_time.to_string(),
+			player.to_owned(),
+			violation.check.to_string(),
+			severity.to_string(),
+			format!("{score:.1}"),
+			format!("{action:?}"),
+			property.clone(),
+			actual.clone(),
+			allowed.clone(),
+			previous_state,
+			updated_state
+		];
+		let line = format!("{}\n", fields.map(|field| escape_field(&field)).join(";"));
+
+		let mut history = self.history.write().await; //also keeps concurrent writes from racing the rotation
+		if let Err(error) = append(&line).await {
+			red_ln!("failed to write {} - {}", Self::FILE_PATH, error);
+		}
+
+		if history.len() == HISTORY_SIZE {
+			history.pop_front();
+		}
+		history.push_back(Entry {
+			timestamp: SystemTime::now(),
+			player: player.to_owned(),
+			check: violation.check,
+			severity,
+			failure: violation.failure.clone()
+		});
+	}
+
+	///most recent first
+	pub async fn recent_violations_of(&self, query: &str, limit: usize) -> Vec<String> {
+		let query = query.to_lowercase();
+
+		self.history
+			.read().await
+			.iter()
+			.rev()
+			.filter(|entry| entry.player.to_lowercase().contains(&query))
+			.take(limit)
+			.map(|entry| format!("{}s ago {} {} ({})", entry.timestamp.elapsed().unwrap_or_default().as_secs(), entry.player, entry.check, entry.failure))
+			.collect()
+	}
+
+	///players with the highest accumulated severity, worst first
+	pub async fn worst_offenders(&self, limit: usize) -> Vec<(String, f32)> {
+		let mut totals = HashMap::<String, f32>::new();
+		for entry in self.history.read().await.iter() {
+			*totals.entry(entry.player.clone()).or_default() += entry.severity;
+		}
+
+		let mut offenders = totals.into_iter().collect::<Vec<_>>();
+		offenders.sort_by(|(_, lhs), (_, rhs)| rhs.total_cmp(lhs));
+		offenders.truncate(limit);
+		offenders
+	}
+}
+
+async fn append(line: &str) -> io::Result<()> {
+	if tokio_fs::metadata(AuditLog::FILE_PATH).await.is_ok_and(|metadata| metadata.len() >= MAX_FILE_SIZE) {
+		tokio_fs::rename(AuditLog::FILE_PATH, AuditLog::ROTATED_FILE_PATH).await?;
+	}
+
+	OpenOptions::new()
+		.create(true)
+		.append(true)
+		.open(AuditLog::FILE_PATH)
+		.await?
+		.write_all(line.as_bytes())
+		.await
+}
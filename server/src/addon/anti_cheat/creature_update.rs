@@ -7,9 +7,9 @@ use boolinator::Boolinator;
 use strum::IntoEnumIterator;
 use tap::Pipe;
 
-use protocol::nalgebra::Point3;
+use protocol::nalgebra::{Point3, Vector3};
 use protocol::packet::common::{CreatureId, EulerAngles, Hitbox, item};
-use protocol::packet::common::item::{Kind, KindDiscriminants};
+use protocol::packet::common::item::{Kind, KindDiscriminants, Stat};
 use protocol::packet::common::Race::*;
 use protocol::packet::creature_update::{Affiliation, Animation, CreatureFlag, PhysicsFlag};
 use protocol::packet::creature_update::Animation::*;
@@ -20,21 +20,69 @@ use protocol::packet::creature_update::skill_tree::Skill;
 use protocol::packet::creature_update::Specialization::*;
 use protocol::utils::{maximum_experience_of, power_of};
 use protocol::utils::constants::combat_classes::*;
-use protocol::utils::constants::{PLAYABLE_RACES, TWO_HANDED_WEAPONS};
+use protocol::utils::constants::{materials, MAX_SPIRIT_OFFSET, MAX_SPIRITS_TWO_HANDED, PLAYABLE_RACES, TWO_HANDED_WEAPONS};
 use protocol::utils::constants::rarity::*;
+use protocol::utils::constants::SIZE_BLOCK;
 use protocol::utils::flagset::FlagSet;
 
 use crate::addon::anti_cheat;
 use crate::addon::anti_cheat::*;
 use crate::addon::anti_cheat::creature_update::animation::animations_avilable_with;
-use crate::addon::anti_cheat::creature_update::equipment::allowed_materials;
+use crate::addon::anti_cheat::creature_update::equipment::{allowed_materials, allowed_weapons, strongest_variants_of};
+use crate::addon::anti_cheat::creature_update::movement::*;
+use crate::addon::anti_cheat::creature_update::progression::*;
 use crate::server::creature::Creature;
 
 mod animation;
 mod equipment;
+mod movement;
+mod progression;
 
-pub(super) fn inspect_position(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
-	Ok(())
+pub(super) async fn inspect_position(previous_state: &Creature, updated_state: &Creature, player: &Player) -> anti_cheat::Result {
+	let ac_data = &mut player.addon_data.write().await.anti_cheat_data;
+
+	let now = Instant::now();
+	let Some(last_position_update) = ac_data.last_position_update.replace(now)
+		else { return Ok(()); };
+
+	if let Some((destination, timestamp)) = ac_data.authorized_teleport {
+		let arrived = (updated_state.position - destination).abs().max() < SIZE_BLOCK * 2;
+		if arrived {
+			//only the jump to the destination is exempt, everything before and after gets validated as usual
+			ac_data.authorized_teleport = Option::None; //glob imports shadow `None`
+			ac_data.movement_debt = 0;
+			ac_data.airborne_gain = 0;
+			return Ok(());
+		}
+		if timestamp.elapsed() > TELEPORT_GRACE_PERIOD {
+			ac_data.authorized_teleport = Option::None;
+		}
+	}
+
+	let respawned = previous_state.health == 0.0 && updated_state.health > 0.0;
+	if respawned {
+		ac_data.movement_debt = 0;
+		ac_data.airborne_gain = 0;
+		return Ok(());
+	}
+
+	let offset = updated_state.position - previous_state.position;
+	let horizontal_distance = offset.xy().cast::<f64>().norm() as i64;
+
+	horizontal_distance.ensure_at_most(TELEPORT_DISTANCE, "position.teleport_distance")?;
+
+	let max_speed = f32::max(max_horizontal_speed(previous_state), max_horizontal_speed(updated_state));
+	let allowed_distance = (f64::from(max_speed) * (now - last_position_update).as_secs_f64() * SIZE_BLOCK as f64) as i64;
+	ac_data.movement_debt = (ac_data.movement_debt + horizontal_distance - allowed_distance).max(-MOVEMENT_CREDIT);
+	ac_data.movement_debt.ensure_at_most(MOVEMENT_TOLERANCE, "position.speed_excess")?;
+
+	if is_grounded(updated_state) {
+		ac_data.airborne_gain = 0;
+		return Ok(());
+	}
+
+	ac_data.airborne_gain += offset.z.max(0);
+	ac_data.airborne_gain.ensure_at_most(AIRBORNE_GAIN, "position.airborne_gain")
 }
 
 pub(super) fn inspect_rotation(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
@@ -53,16 +101,13 @@ pub(super) fn inspect_rotation(previous_state: &Creature, updated_state: &Creatu
 }
 
 pub(super) fn inspect_velocity(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
-	Ok(())
+	let max_speed = f32::max(max_horizontal_speed(previous_state), max_horizontal_speed(updated_state));
+
+	updated_state.velocity.xy()
+		.magnitude()
+		.ensure_at_most(max_speed, "velocity.horizontal")
 }
 pub(super) fn inspect_acceleration(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
-	//todo: investigate false positives
-//	let limit_xy = Vector3::<f32>::new(80.0, 80.0, 0.0).magnitude() + 0.00001; //113,1370849898476; //todo: would epsilon suffice?
-//	let actual_xy = acceleration.xy().magnitude();
-//	if !updated_state.flags.get(CreatureFlag::Gliding) {
-//		actual_xy.ensure_within(&(0.0..=limit_xy), "acceleration.horizontal")?;
-//	}
-
 	if updated_state.flags_physics.get(PhysicsFlag::Swimming) {
 		updated_state.acceleration.z.ensure_within(&(-80.0..=80.0), "acceleration.vertical")
 	} else if updated_state.flags.get(CreatureFlag::Climbing) || previous_state.flags.get(CreatureFlag::Climbing) {//possible fix for a false positive
@@ -72,6 +117,18 @@ pub(super) fn inspect_acceleration(previous_state: &Creature, updated_state: &Cr
 	}
 }
 
+///todo: investigate false positives, only logged until then
+pub(super) fn inspect_acceleration_horizontal(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
+	let limit_xy = Vector3::<f32>::new(80.0, 80.0, 0.0).magnitude() + 0.00001; //113,1370849898476; //todo: would epsilon suffice?
+	if is_grounded(updated_state) && !updated_state.flags_physics.get(PhysicsFlag::OnGround) {//gliding, riding etc. accelerate differently
+		return Ok(());
+	}
+
+	updated_state.acceleration.xy()
+		.magnitude()
+		.ensure_at_most(limit_xy, "acceleration.horizontal")
+}
+
 pub(super) fn inspect_velocity_extra(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
 	let (max_xy, max_z): (f32, f32) =
 		match updated_state.occupation {
@@ -208,6 +265,8 @@ pub(super) async fn inspect_combo_timeout(previous_state: &Creature, updated_sta
 
 #[expect(clippy::too_many_lines, reason = "TODO")] //TODO: extract constants
 pub(super) fn inspect_appearance(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
+	updated_state.appearance.ensure_exact(&previous_state.appearance, "appearance.changed")?; //can only be picked during character creation
+	//todo: hair_color, the palette differs between races
 	updated_state.appearance.flags.ensure_exact(&FlagSet::default(), "appearance.flags")?;
 
 	updated_state.appearance.tail_model.ensure_exact(&-1, "appearance.tail_model")?;
@@ -628,7 +687,7 @@ pub(super) fn inspect_unknown32(previous_state: &Creature, updated_state: &Creat
 
 pub(super) fn inspect_level(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
 	updated_state.level
-		.ensure_within(&(1..=500), "level")
+		.ensure_within(&(1..=MAX_LEVEL), "level")
 }
 
 pub(super) fn inspect_experience(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
@@ -637,6 +696,25 @@ pub(super) fn inspect_experience(previous_state: &Creature, updated_state: &Crea
 		.ensure_within(&(0..maximum), "experience")
 }
 
+///level and experience may only grow by what the server granted
+pub(super) async fn inspect_progression(previous_state: &Creature, updated_state: &Creature, player: &Player) -> anti_cheat::Result {
+	let gained_experience = total_experience_of(updated_state) - total_experience_of(previous_state);
+	if gained_experience <= 0 {
+		return Ok(());
+	}
+
+	let granted_experience = player.addon_data.read().await.anti_cheat_data.granted_experience;
+	gained_experience.ensure_at_most(granted_experience, "experience.gained")
+}
+
+///consumes the granted experience claimed by an update, once all of it passed inspection
+pub(super) async fn claim_experience(previous_state: &Creature, updated_state: &Creature, player: &Player) {
+	let gained_experience = total_experience_of(updated_state) - total_experience_of(previous_state);
+	if gained_experience > 0 {
+		player.addon_data.write().await.anti_cheat_data.granted_experience -= gained_experience;
+	}
+}
+
 pub(super) fn inspect_master(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
 	updated_state.master
 		.ensure_exact(&CreatureId(0), "master")
@@ -709,44 +787,75 @@ pub(super) fn inspect_equipment(previous_state: &Creature, updated_state: &Creat
 			continue; //empty item slots contain uninitialized memory
 		}
 
-		let property_name = |literal| { format!("equipment[{slot:?}].{literal}") };
+		let property_name = |literal: &str| { format!("equipment[{slot:?}].{literal}") };
 
 		item.as_formula
 			.ensure_exact(&false, &property_name("as_formula"))?;
 		item.kind.pipe(KindDiscriminants::from)
 			.ensure_one_of(allowed, &property_name("kind"))?;
 		item.rarity
-			.ensure_at_most(LEGENDARY, &property_name("rarity"))?;
+			.ensure_within(&(NORMAL..=LEGENDARY), &property_name("rarity"))?;
 		item.material
 			.ensure_one_of(allowed_materials(item.kind, updated_state.occupation), &property_name("material"))?;
-		(item.level as i32).pipe(power_of)
-			.ensure_within(&(0..=power_of(updated_state.level)), &property_name("power"))?;
-		item.spirit_counter
-			.ensure_within(&(0..=32), &property_name("spirit_counter"))?;
+		i32::from(item.level)
+			.ensure_within(&(1..=updated_state.level), &property_name("level"))?;
+		if let Kind::Weapon(weapon) = item.kind {
+			weapon.ensure_one_of(&allowed_weapons(updated_state.occupation), &property_name("kind"))?;
+		}
+
 		//normally only 2h weapons can have more than 16 (up to 32) spirits, but we're tolerating 32 on everyhting due to popularity
+		item.spirit_counter
+			.ensure_within(&(0..=MAX_SPIRITS_TWO_HANDED), &property_name("spirit_counter"))?;
+		let spirits = &item.spirits[..item.spirit_counter.unsigned_abs() as usize];
+		for (index, spirit) in spirits.iter().enumerate() {
+			spirit.material
+				.ensure_one_of(&materials::SPIRITS, &property_name(&format!("spirits[{index}].material")))?;
+			spirit.level
+				.ensure_within(&(0..=item.level), &property_name(&format!("spirits[{index}].level")))?;
+			for coordinate in spirit.position.iter() {
+				coordinate
+					.ensure_within(&(-MAX_SPIRIT_OFFSET..=MAX_SPIRIT_OFFSET), &property_name(&format!("spirits[{index}].position")))?;
+			}
+			spirits[..index]
+				.iter()
+				.any(|other| other.position == spirit.position)
+				.ensure_exact(&false, &property_name(&format!("spirits[{index}].position_taken")))?;
+		}
+
+		let stats = item.stats();
+		let variant_stats = strongest_variants_of(item, updated_state.occupation, updated_state.level)
+			.iter()
+			.map(Item::stats)
+			.collect::<Vec<_>>();
+		for stat in Stat::iter() {
+			let ceiling = variant_stats
+				.iter()
+				.map(|variant| variant[stat])
+				.fold(0.0, f32::max);
+			stats[stat]
+				.ensure_at_most(ceiling, &property_name(&format!("stats.{stat:?}")))?;
+		}
 
 		//item.flags
-		//item.spirits //tolerating everything due to popularity
 		//item.seed
 		//	.ensure_not_negative(&format!("equipment[{:?}].seed", slot)) //tolerating negative seeds due to popularity
 	}
 
-	updated_state
-		.equipment
-		.iter()
-		.map(|item| {
-			let Kind::Weapon(weapon) = item.kind
-				else { return 0; };
+	for (hand, other_hand) in [(Slot::LeftWeapon, Slot::RightWeapon), (Slot::RightWeapon, Slot::LeftWeapon)] {
+		let Kind::Weapon(weapon) = updated_state.equipment[hand].kind
+			else { continue; };
 
-			if TWO_HANDED_WEAPONS.contains(&weapon) { 2 } else { 1 }
-		})
-		.sum::<usize>()
-		.ensure_at_most(2, "equipment.weapon_hand_count")?;
+		if TWO_HANDED_WEAPONS.contains(&weapon) {
+			updated_state.equipment[other_hand].kind
+				.ensure_exact(&Kind::Void, &format!("equipment[{other_hand:?}].kind"))?;
+		}
+	}
 
 	Ok(())
 }
 
 pub(super) fn inspect_name(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
+	updated_state.name.ensure_exact(&previous_state.name, "name.changed")?; //availability is checked once on join
 	//character names are serialized as a cstring and thus guaranteed to be comprised of single-byte characters exclusively
 	updated_state.name.chars().count().ensure_within(&(1..=15), "name.length")?;
 	for (n, character) in updated_state.name.chars().enumerate() {
@@ -764,7 +873,15 @@ pub(super) fn inspect_skill_tree(previous_state: &Creature, updated_state: &Crea
 			.ensure_not_negative(&format!("skill_tree.{skill:?}"))?;
 	}
 	updated_state.skill_tree.iter().sum::<i32>()
-		.ensure_at_most((updated_state.level - 1) * 2, "skill_tree.total")
+		.ensure_at_most((updated_state.level - 1) * SKILL_POINTS_PER_LEVEL, "skill_tree.total")
+}
+
+pub(super) fn inspect_skill_caps(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
+	for skill in Skill::iter() {
+		updated_state.skill_tree[skill]
+			.ensure_at_most(max_points_in(skill), &format!("skill_tree.{skill:?}"))?;
+	}
+	Ok(())
 }
 
 pub(super) fn inspect_mana_cubes(previous_state: &Creature, updated_state: &Creature) -> anti_cheat::Result {
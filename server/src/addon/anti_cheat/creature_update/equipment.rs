@@ -1,46 +1,22 @@
-use protocol::packet::common::item;
+use protocol::packet::common::{item, Item};
 use protocol::packet::common::item::Kind::*;
-use protocol::packet::common::item::kind::Weapon::*;
+use protocol::packet::common::item::kind::Weapon;
 use protocol::packet::common::item::Material;
-use protocol::packet::common::item::Material::*;
 use protocol::packet::creature_update::Occupation;
-use protocol::packet::creature_update::Occupation::*;
-use protocol::utils::constants::materials;
+use protocol::utils::constants::{materials, weapons, MAX_SPIRITS_TWO_HANDED};
+use protocol::utils::constants::rarity::LEGENDARY;
+
+///seeds at which the generator picks the extremes of both stat balances (health/regeneration and crit/tempo)
+const EXTREME_SEEDS: [i32; 3] = [0, 13, 20];
 
 pub fn allowed_materials(item_kind: item::Kind, occupation: Occupation) -> &'static [Material] {
 	match item_kind {
-		Weapon(Sword)      => &materials::SWORD[..],
-		Weapon(Axe)        => &materials::AXE[..],
-		Weapon(Mace)       => &materials::MACE[..],
-		Weapon(Dagger)     => &materials::DAGGER[..],
-		Weapon(Fist)       => &materials::FIST[..],
-		Weapon(Longsword)  => &materials::LONGSWORD[..],
-		Weapon(Bow)        => &materials::BOW[..],
-		Weapon(Crossbow)   => &materials::CROSSBOW[..],
-		Weapon(Boomerang)  => &materials::BOOMERANG[..],
-		Weapon(Arrow)      => &materials::ARROW[..],
-		Weapon(Staff)      => &materials::STAFF[..],
-		Weapon(Wand)       => &materials::WAND[..],
-		Weapon(Bracelet)   => &materials::BRACELET[..],
-		Weapon(Shield)     => &materials::SHIELD[..],
-		Weapon(Quiver)     => &materials::QUIVER[..],
-		Weapon(Greatsword) => &materials::GREATSWORD[..],
-		Weapon(Greataxe)   => &materials::GREATAXE[..],
-		Weapon(Greatmace)  => &materials::GREATMACE[..],
-		Weapon(Pitchfork)  => &materials::PITCHFORK[..],
-		Weapon(Pickaxe)    => &materials::PICKAXE[..],
-		Weapon(Torch)      => &materials::TORCH[..],
+		Weapon(weapon) => lookup(&materials::BY_WEAPON, weapon).unwrap_or(&[]),
 
 		Chest    |
 		Boots    |
 		Gloves   |
-		Shoulder => match occupation {
-			Warrior => &[Bone, Mammoth, Gold, Iron, Obsidian, Saurian, Ice][..],
-			Ranger  => &[Bone, Mammoth, Gold, Parrot, Linen][..],
-			Mage    => &[Bone, Mammoth, Gold, Licht, Silk][..],
-			Rogue   => &[Bone, Mammoth, Gold, Cotton][..],
-			_       => &[Bone, Mammoth, Gold][..]
-		},//todo: burn it with fire
+		Shoulder => lookup(&materials::ARMOR_BY_OCCUPATION, occupation).unwrap_or(&materials::ARMOR_OTHER),
 
 		Amulet     |
 		Ring       => &materials::ACCESSORIES[..],
@@ -51,4 +27,36 @@ pub fn allowed_materials(item_kind: item::Kind, occupation: Occupation) -> &'sta
 
 		_          => &[Material::None][..],
 	}
-}
\ No newline at end of file
+}
+
+pub fn allowed_weapons(occupation: Occupation) -> Vec<Weapon> {
+	let class_weapons = lookup(&weapons::BY_OCCUPATION, occupation).unwrap_or(&[]);
+
+	[class_weapons, &weapons::TOOLS[..]].concat()
+}
+
+///the strongest items of the same kind the generator could produce for a character of `level`.
+///their stats bound those of `item`, independently of its own level, rarity, seed and spirits
+pub fn strongest_variants_of(item: &Item, occupation: Occupation, level: i32) -> Vec<Item> {
+	let mut variants = vec![];
+	for &material in allowed_materials(item.kind, occupation) {
+		for seed in EXTREME_SEEDS {
+			variants.push(Item {
+				seed,
+				material,
+				level: level.try_into().unwrap_or(i16::MAX),
+				rarity: LEGENDARY,
+				spirit_counter: MAX_SPIRITS_TWO_HANDED,
+				..item.clone()
+			});
+		}
+	}
+	variants
+}
+
+fn lookup<Key: PartialEq + Copy, Value: ?Sized>(table: &[(Key, &'static Value)], key: Key) -> Option<&'static Value> {
+	table
+		.iter()
+		.find(|(candidate, _)| *candidate == key)
+		.map(|(_, value)| *value)
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use protocol::packet::creature_update::Animation::{Riding, Sail};
+use protocol::packet::creature_update::CreatureFlag::{Climbing, Gliding};
+use protocol::packet::creature_update::PhysicsFlag::{OnGround, Swimming};
+use protocol::utils::constants::SIZE_BLOCK;
+
+use crate::server::creature::Creature;
+
+///no single update can cover this much ground, even gliding (the fastest movement at 40 blocks per second) would take most of a second
+pub const TELEPORT_DISTANCE: i64 = SIZE_BLOCK * 32;
+///how far ahead of the allowed speed a player may get, to compensate for jitter
+pub const MOVEMENT_TOLERANCE: i64 = SIZE_BLOCK * 4;
+///how much unused movement can be saved up, to compensate for packets arriving in bursts after a lag spike
+pub const MOVEMENT_CREDIT: i64 = SIZE_BLOCK * 32;
+///how much height can be gained between touching the ground and landing again
+pub const AIRBORNE_GAIN: i64 = SIZE_BLOCK * 8;
+///how long a teleport destination stays authorized for the player to arrive at
+pub const TELEPORT_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+///in blocks per second
+pub fn max_horizontal_speed(creature: &Creature) -> f32 {
+	let base_speed =
+		if creature.animation == Riding {
+			30.0
+		} else if creature.animation == Sail {
+			20.0
+		} else if creature.flags.get(Gliding) {
+			40.0
+		} else if creature.flags.get(Climbing) {
+			6.0
+		} else if creature.flags_physics.get(Swimming) {
+			8.0
+		} else {
+			14.0
+		};
+
+	let dodge_speed = if creature.effect_time_dodge > 0 { 30.0 } else { 0.0 };
+	let retreat_speed = creature.velocity_extra.xy().magnitude(); //ranger ability
+
+	f32::max(base_speed, dodge_speed) + retreat_speed
+}
+
+///whether vertical movement is currently supported by something other than a jump
+pub fn is_grounded(creature: &Creature) -> bool {
+	creature.flags_physics.get(OnGround) ||
+	creature.flags_physics.get(Swimming) ||
+	creature.flags.get(Climbing) ||
+	creature.flags.get(Gliding) ||
+	creature.animation == Riding ||
+	creature.animation == Sail
+}
@@ -0,0 +1,36 @@
+use protocol::packet::creature_update::skill_tree::Skill;
+use protocol::packet::creature_update::skill_tree::Skill::*;
+use protocol::utils::maximum_experience_of;
+
+use crate::server::creature::Creature;
+
+pub const MAX_LEVEL: i32 = 500;
+pub const SKILL_POINTS_PER_LEVEL: i32 = 2;
+
+///how far the skill menu lets each skill be raised.
+///only logged by default (see `Check::SkillCap`)
+//todo: confirm in game, then escalate the policy
+pub const fn max_points_in(skill: Skill) -> i32 {
+	match skill {
+		PetMaster   |
+		PetRiding   |
+		Sailing     |
+		Climbing    |
+		HangGliding |
+		Swimming    => 10,
+
+		Aility1  |
+		Ability2 |
+		Ability3 |
+		Ability4 |
+		Ability5 => 20
+	}
+}
+
+///experience accumulated since level 1
+pub fn total_experience_of(creature: &Creature) -> i64 {
+	(1..creature.level)
+		.map(|level| i64::from(maximum_experience_of(level)))
+		.sum::<i64>()
+		+ i64::from(creature.experience)
+}
@@ -0,0 +1,132 @@
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::atomic::Ordering::Relaxed;
+use std::time::{Duration, Instant};
+
+use colour::yellow_ln;
+use tokio::sync::RwLock;
+
+use crate::addon::anti_cheat;
+use crate::addon::anti_cheat::audit_log::AuditLog;
+use crate::addon::anti_cheat::fingerprint::Fingerprints;
+use crate::addon::anti_cheat::naming::NameRules;
+use crate::addon::anti_cheat::violation::{Action, Check, Violation};
+use crate::server::player::Player;
+use crate::server::Server;
+
+const BAN_DURATION: Duration = Duration::from_secs(30 * 60);
+
+///decides what happens to players whose packets failed inspection
+#[derive(Debug, Default)]
+pub struct Enforcement {
+	///violations of these checks only get logged, to investigate false positives without affecting anyone
+	shadowed_checks: RwLock<HashSet<Check>>,
+	temp_bans: RwLock<HashMap<IpAddr, Instant>>,
+	pub audit_log: AuditLog,
+	pub fingerprints: Fingerprints,
+	pub name_rules: NameRules
+}
+
+impl Enforcement {
+	///returns whether the offending packet should be discarded
+	pub async fn report(&self, server: &Server, player: &Player, violation: Violation) -> bool {
+		let policy = &server.config.anti_cheat_policies[&violation.check];
+		let score = player.addon_data.write().await.anti_cheat_data.add_violation(violation.check, policy.severity);
+		let action = policy.action_at(score);
+		let name = player.character.read().await.name.clone();
+		let summary = format!("{name} failed {} check ({}), score {score:.1} -> {action:?}", violation.check, violation.failure);
+
+		self.audit_log.record(&name, &violation, policy.severity, score, action).await;
+		if server.config.mirror_violations_to_discord {
+			server.addons.discord_integration.post(&format!("[anti-cheat] {summary}"), true).await;
+		}
+
+		if self.shadowed_checks.read().await.contains(&violation.check) {
+			yellow_ln!("[shadow] {}", summary);
+			return false;
+		}
+		yellow_ln!("{}", summary);
+
+		match action {
+			Action::LogOnly => return false,
+			Action::NotifyAdmins => {
+				notify_admins(server, &summary).await;
+			}
+			Action::RubberBand => {
+				let last_valid_position = player.character.read().await.position; //the offending packet never got applied
+				let server_static = server.extend_lifetime();
+				let player_id = player.id;
+				tokio::spawn(async move {//teleports take a while to settle
+					if let Some(target) = server_static.find_player_by_id(player_id).await {
+						server_static.teleport(&target, last_valid_position).await;
+					}
+				});
+			}
+			Action::Kick => {
+				notify_admins(server, &summary).await;
+				server.kick(player, format!("of anti-cheat ({})", violation.check)).await;
+			}
+			Action::TempBan => {
+				notify_admins(server, &summary).await;
+				self.temp_bans.write().await.insert(player.address.ip(), Instant::now() + BAN_DURATION);
+				server.kick(player, format!("of anti-cheat ({}), banned for {} minutes", violation.check, BAN_DURATION.as_secs() / 60)).await;
+			}
+		}
+
+		true
+	}
+
+	///decides whether a freshly connected player may join, and tells them why not
+	pub async fn admit(&self, server: &Server, player: &Player) -> Result<(), &'static str> {
+		let character = player.character.read().await.clone();
+		player.addon_data.write().await.anti_cheat_data.on_join(&character);
+
+		if let Err(violation) = anti_cheat::inspect_join(&character, server.config.max_join_level)
+			&& self.report(server, player, violation).await
+		{
+			return Err("character failed inspection");
+		}
+
+		if !self.fingerprints.on_join(server, player).await {
+			return Err("character was modified while offline");
+		}
+
+		if let Err(reason) = self.name_rules.claim(&character.name) {
+			player.notify(format!("please choose a different name: {reason}")).await;
+			return Err(reason);
+		}
+
+		Ok(())
+	}
+
+	pub async fn is_banned(&self, address: IpAddr) -> bool {
+		let mut temp_bans = self.temp_bans.write().await;
+		temp_bans.retain(|_, expiry| *expiry > Instant::now());
+		temp_bans.contains_key(&address)
+	}
+
+	///returns whether the check is shadowed now
+	pub async fn toggle_shadow_mode(&self, check: Check) -> bool {
+		let mut shadowed_checks = self.shadowed_checks.write().await;
+		if shadowed_checks.remove(&check) {
+			return false;
+		}
+		shadowed_checks.insert(check);
+		true
+	}
+
+	pub async fn shadowed_checks(&self) -> Vec<Check> {
+		self.shadowed_checks.read().await.iter().copied().collect()
+	}
+}
+
+pub(super) async fn notify_admins(server: &Server, message: &str) {
+	for player in server.players.read().await.iter() {
+		if player.admin.load(Relaxed) {
+			player.notify(format!("[anti-cheat] {message}")).await;
+		}
+	}
+	if !server.config.mirror_violations_to_discord { //otherwise it has been posted already
+		server.addons.discord_integration.post(&format!("[anti-cheat] {message}"), true).await;
+	}
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::str::FromStr;
+
+use colour::{red_ln, yellow_ln};
+use tokio::sync::RwLock;
+
+use protocol::packet::common::item::Kind::Void;
+use protocol::WriteCwData;
+
+use crate::addon::anti_cheat::enforcement::notify_admins;
+use crate::server::creature::Creature;
+use crate::server::player::Player;
+use crate::server::Server;
+use crate::server::utils::{escape_field, split_fields};
+
+use self::Policy::*;
+
+///what happens when a character comes back different from how it left
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Policy {
+	Allow,
+	WarnAdmins,
+	Reject
+}
+
+impl FromStr for Policy {
+	type Err = String;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		match name {
+			"allow"       => Ok(Allow),
+			"warn_admins" => Ok(WarnAdmins),
+			"reject"      => Ok(Reject),
+			_ => Err(format!("unknown policy {name}, expected allow, warn_admins or reject"))
+		}
+	}
+}
+
+///the parts of a character that can't legitimately change while its player is offline.
+///enums are stored by name to keep the file readable
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Fingerprint {
+	class: String,
+	race: String,
+	level: i32,
+	///FNV-1a over the items in their wire format
+	equipment_hash: u64,
+	skill_tree: Vec<i32>
+}
+
+impl Fingerprint {
+	async fn of(character: &Creature) -> Self {
+		let mut equipment = vec![];
+		for item in character.equipment.iter() {
+			if item.kind == Void {
+				equipment.push(0); //empty item slots contain uninitialized memory
+				continue;
+			}
+			equipment.write_cw_data(item).await.expect("writing to memory is infallible");
+		}
+
+		Self {
+			class: format!("{:?}/{:?}", character.occupation, character.specialization),
+			race: format!("{:?}", character.race),
+			level: character.level,
+			equipment_hash: fnv1a(&equipment),
+			skill_tree: character.skill_tree.iter().copied().collect()
+		}
+	}
+
+	fn parse(line: &str) -> Option<(String, Self)> {
+		let [name, class, race, level, equipment_hash, skill_tree]: [String; 6] = split_fields(line)
+			.try_into()
+			.ok()?;
+
+		let fingerprint = Self {
+			class,
+			race,
+			level: level.parse().ok()?,
+			equipment_hash: equipment_hash.parse().ok()?,
+			skill_tree: skill_tree.split(',').map(str::parse).collect::<Result<_, _>>().ok()?
+		};
+
+		Some((name, fingerprint))
+	}
+
+	fn serialize(&self, name: &str) -> String {
+		let skill_tree = self.skill_tree.iter().map(ToString::to_string).collect::<Vec<_>>().join(",");
+		format!("{};{};{};{};{};{skill_tree}", escape_field(name), self.class, self.race, self.level, self.equipment_hash)
+	}
+
+	fn differences_to(&self, other: &Self) -> Vec<String> {
+		let mut differences = vec![];
+		if self.class != other.class {
+			differences.push(format!("class {} -> {}", self.class, other.class));
+		}
+		if self.race != other.race {
+			differences.push(format!("race {} -> {}", self.race, other.race));
+		}
+		if self.level != other.level {
+			differences.push(format!("level {} -> {}", self.level, other.level));
+		}
+		if self.equipment_hash != other.equipment_hash {
+			differences.push("equipment".to_owned());
+		}
+		if self.skill_tree != other.skill_tree {
+			differences.push(format!("skill tree {:?} -> {:?}", self.skill_tree, other.skill_tree));
+		}
+		differences
+	}
+}
+
+///remembers each character (by name) as it was when its player last disconnected
+#[derive(Debug)]
+pub struct Fingerprints {
+	known: RwLock<HashMap<String, Fingerprint>>
+}
+
+impl Default for Fingerprints {
+	fn default() -> Self {
+		let file_content = match fs::read_to_string(Self::FILE_PATH) {
+			Ok(content) => content,
+			Err(error) if error.kind() == NotFound => String::new(),
+			Err(error) => panic!("failed to load {} - {}", Self::FILE_PATH, error)
+		};
+
+		Self {
+			known: file_content
+				.lines()
+				.filter_map(Fingerprint::parse)
+				.collect::<HashMap<_, _>>()
+				.into()
+		}
+	}
+}
+
+impl Fingerprints {
+	const FILE_PATH: &'static str = "fingerprints.csv";
+
+	///returns whether the player may stay
+	pub async fn on_join(&self, server: &Server, player: &Player) -> bool {
+		let character = player.character.read().await.clone();
+		let Some(previous) = self.known.read().await.get(&character.name).cloned()
+			else { return true; };
+
+		let differences = previous.differences_to(&Fingerprint::of(&character).await);
+		if differences.is_empty() {
+			return true;
+		}
+
+		let summary = format!("{} changed while offline: {}", character.name, differences.join(", "));
+		yellow_ln!("{}", summary);
+
+		match server.config.fingerprint_policy {
+			Allow => true,
+			WarnAdmins => {
+				notify_admins(server, &summary).await;
+				true
+			}
+			Reject => {
+				notify_admins(server, &summary).await;
+				player.notify("your character was modified since you last played here").await;
+				false
+			}
+		}
+	}
+
+	pub async fn on_leave(&self, player: &Player) {
+		let character = player.character.read().await.clone();
+		let current = Fingerprint::of(&character).await;
+		let mut known = self.known.write().await;
+		known.insert(character.name, current);
+
+		let file_content = known
+			.iter()
+			.map(|(name, fingerprint)| fingerprint.serialize(name))
+			.collect::<Vec<_>>()
+			.join("\n");
+		drop(known);
+
+		if let Err(error) = fs::write(Self::FILE_PATH, file_content) {
+			red_ln!("failed to write {} - {}", Self::FILE_PATH, error);
+		}
+	}
+}
+
+///stable across platforms and compiler versions, unlike [`std::hash::DefaultHasher`]
+fn fnv1a(bytes: &[u8]) -> u64 {
+	const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+	const PRIME: u64 = 0x0100_0000_01b3;
+
+	bytes
+		.iter()
+		.fold(OFFSET_BASIS, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(PRIME))
+}
@@ -0,0 +1,109 @@
+use std::time::{Duration, Instant};
+
+use protocol::packet::common::item::Kind::Weapon;
+use protocol::packet::common::item::kind::Weapon::*;
+use protocol::packet::common::item::Stat;
+use protocol::packet::creature_update::equipment::Slot::{LeftWeapon, RightWeapon};
+use protocol::packet::creature_update::multipliers::Multiplier::AttackSpeed;
+use protocol::packet::creature_update::Occupation::*;
+use protocol::packet::Hit;
+use protocol::utils::constants::SIZE_BLOCK;
+use protocol::utils::{level_scaling_factor, rarity_scaling_factor};
+
+use crate::addon::anti_cheat;
+use crate::addon::anti_cheat::{EnsureAtMost, EnsureOneOf, EnsureWithin};
+use crate::server::creature::Creature;
+use crate::server::player::Player;
+
+///how far the reported impact may be from where the server thinks the target is.
+///the target keeps moving while its position updates are on the way, so this is about a second of running
+const IMPACT_TOLERANCE: i64 = SIZE_BLOCK * 8;
+///covers ability damage multipliers and buffs
+const MAX_DAMAGE_MULTIPLIER: f32 = 8.0;
+///critical hits deal double damage
+const CRITICAL_MULTIPLIER: f32 = 2.0;
+///in milliseconds. the longest stuns (shield bash, charged attacks) last a few seconds, this leaves room for stacking
+const MAX_STUNTIME: i32 = 10_000;
+///time between two hits on the same target at base attack speed (dagger combos are the fastest)
+const BASE_HIT_INTERVAL: Duration = Duration::from_millis(200);
+///multi-hit abilities (e.g. shuriken) hit the same target several times during a single animation
+const MAX_BURST: f32 = 8.0;
+
+pub async fn inspect(source: &Player, target: &Player, hit: &Hit) -> anti_cheat::Result {
+	hit.attacker.ensure_exact(&source.id, "hit.attacker")?;
+
+	let attacker = source.character.read().await.clone();
+	let target = target.character.read().await.clone();
+
+	let attack_distance = (target.position - attacker.position).cast::<f64>().norm() as i64;
+	attack_distance.ensure_at_most(max_range_of(&attacker), "hit.range")?;
+
+	let impact_offset = (hit.position - target.position).cast::<f64>().norm() as i64;
+	impact_offset.ensure_at_most(IMPACT_TOLERANCE, "hit.position")?;
+
+	let max_damage = maximum_damage_of(&attacker) * if hit.critical { CRITICAL_MULTIPLIER } else { 1.0 };
+	hit.damage.abs().ensure_at_most(max_damage, "hit.damage")?; //negative for heals
+	hit.stuntime.ensure_within(&(0..=MAX_STUNTIME), "hit.stuntime")?;
+
+	//every target has a budget of hits that refills at the attacker's attack speed.
+	//area of effect abilities are fine, as each target is budgeted separately
+	let interval = hit_interval_of(&attacker);
+	let ac_data = &mut source.addon_data.write().await.anti_cheat_data;
+	let now = Instant::now();
+	ac_data.hit_budgets.retain(|_, (_, last_hit)| now - *last_hit < interval.mul_f32(MAX_BURST)); //these are full again
+
+	let (budget, last_hit) = ac_data.hit_budgets.entry(hit.target).or_insert((MAX_BURST, now));
+	let refill = (now - *last_hit).as_secs_f32() / interval.as_secs_f32();
+	*budget = (*budget + refill).min(MAX_BURST) - 1.0;
+	*last_hit = now;
+
+	(*budget).ensure_within(&(0.0..), "hit.rate_budget")
+}
+
+///shortest sustainable time between two hits on the same target, given the attacker's attack speed
+fn hit_interval_of(attacker: &Creature) -> Duration {
+	let tempo = attacker.equipment
+		.iter()
+		.map(|item| item.stats()[Stat::Tempo])
+		.sum::<f32>();
+	let attack_speed = attacker.multipliers[AttackSpeed].max(1.0) * (1.0 + tempo);
+
+	BASE_HIT_INTERVAL.div_f32(attack_speed)
+}
+
+fn max_range_of(attacker: &Creature) -> i64 {
+	let weapon_range = [LeftWeapon, RightWeapon]
+		.iter()
+		.map(|slot| match attacker.equipment[*slot].kind {
+			Weapon(Bow)      |
+			Weapon(Crossbow) => 120,
+
+			Weapon(Boomerang) |
+			Weapon(Staff)     |
+			Weapon(Wand)      |
+			Weapon(Bracelet)  => 80,
+
+			_ => 16
+		})
+		.max()
+		.unwrap_or_default();
+
+	let ability_range =
+		match attacker.occupation {
+			Rogue => 60, //shuriken
+			Mage  => 80,
+			_     => 0
+		};
+
+	SIZE_BLOCK * i64::max(weapon_range, ability_range)
+}
+
+pub(super) fn maximum_damage_of(attacker: &Creature) -> f32 {
+	let innate_damage = level_scaling_factor(attacker.level as f32) * rarity_scaling_factor(4); //unarmed
+	let weapon_damage = [LeftWeapon, RightWeapon]
+		.iter()
+		.map(|slot| attacker.equipment[*slot].stats()[Stat::Damage])
+		.sum::<f32>();
+
+	(innate_damage + weapon_damage) * MAX_DAMAGE_MULTIPLIER
+}
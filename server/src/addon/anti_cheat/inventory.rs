@@ -0,0 +1,57 @@
+use std::collections::{HashMap, HashSet};
+
+use protocol::packet::common::item::Kind::Void;
+use protocol::packet::common::Item;
+
+use crate::addon::anti_cheat;
+use crate::addon::anti_cheat::EnsureOneOf;
+use crate::server::creature::Creature;
+use crate::server::player::Player;
+use crate::server::Server;
+
+///the equipment a player joined with and every item the server handed to them during this session
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+	///how many of each item the player should still have
+	owned: HashMap<Item, usize>,
+	///items of unknown origin that have been let through already. the bags can't hold more than one of them
+	///without the server knowing, so dropping the same item again has to be covered by `owned`
+	trusted: HashSet<Item>
+}
+
+impl Ledger {
+	///clients only ever tell the server about their equipment, the contents of their bags remain unknown
+	pub fn seed(&mut self, character: &Creature) {
+		character.equipment
+			.iter()
+			.filter(|item| item.kind != Void) //empty item slots contain uninitialized memory
+			.for_each(|item| self.receive(item.clone()));
+	}
+
+	pub fn receive(&mut self, item: Item) {
+		*self.owned.entry(item).or_default() += 1;
+	}
+
+	///returns whether the player could have owned this item.
+	///items of unknown origin might be from the initial inventory, which only `trust_unknown` lets through, once
+	pub fn release(&mut self, item: &Item, trust_unknown: bool) -> bool {
+		if let Some(count) = self.owned.get_mut(item) {
+			*count -= 1;
+			if *count == 0 {
+				self.owned.remove(item);
+			}
+			return true;
+		}
+
+		trust_unknown && self.trusted.insert(item.clone())
+	}
+}
+
+pub async fn inspect_drop(server: &Server, source: &Player, item: &Item) -> anti_cheat::Result {
+	source.addon_data
+		.write().await
+		.anti_cheat_data
+		.inventory
+		.release(item, server.config.trust_initial_inventory)
+		.ensure_exact(&true, &format!("dropped_item({:?}).accounted_for", item.kind))
+}
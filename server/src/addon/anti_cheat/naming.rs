@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::sync::{Mutex, PoisonError};
+
+use tap::Tap;
+
+///prefix of server messages like `[+]` and `[anti-cheat]`
+const SERVER_PREFIX: char = '[';
+
+///decides which names can be picked, on top of the character set restrictions enforced by `inspect_name`
+#[derive(Debug)]
+pub struct NameRules {
+	///names consisting of or containing any of these as a word are rejected. one per line, case-insensitive
+	reserved: Vec<String>,
+	///skeletons of the names currently in use
+	taken: Mutex<HashSet<String>>
+}
+
+impl Default for NameRules {
+	fn default() -> Self {
+		let file_content = match fs::read_to_string(Self::FILE_PATH) {
+			Ok(content) => content,
+
+			Err(error) if error.kind() == NotFound => {
+				"server\nadmin\nberld"
+					.tap(|content| fs::write(Self::FILE_PATH, content).unwrap())
+					.to_owned()
+			}
+
+			Err(error) => panic!("failed to load {} - {}", Self::FILE_PATH, error)
+		};
+
+		Self {
+			reserved: file_content
+				.lines()
+				.map(str::trim)
+				.filter(|line| !line.is_empty())
+				.map(skeleton_of)
+				.collect(),
+			taken: Mutex::default()
+		}
+	}
+}
+
+impl NameRules {
+	const FILE_PATH: &'static str = "reserved_names.txt";
+
+	///reserves `name` until [`Self::release`], or returns the reason why it can't be used
+	pub fn claim(&self, name: &str) -> Result<(), &'static str> {
+		if name.starts_with(SERVER_PREFIX) {
+			return Err("names must not look like server messages");
+		}
+
+		let skeleton = skeleton_of(name);
+		if self.reserved.iter().any(|reserved| is_reserved(&skeleton, reserved)) {
+			return Err("this name is reserved");
+		}
+
+		//checking and inserting under the same lock, otherwise two players could join with the same name at once
+		if !self.taken.lock().unwrap_or_else(PoisonError::into_inner).insert(skeleton) {
+			return Err("this name (or one that looks just like it) is already taken");
+		}
+
+		Ok(())
+	}
+
+	pub fn release(&self, name: &str) {
+		self.taken
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.remove(&skeleton_of(name));
+	}
+}
+
+///whether `skeleton` is `reserved` or has it as one of its words, so "server" rejects "Server.Bot" but not "Observer"
+pub fn is_reserved(skeleton: &str, reserved: &str) -> bool {
+	skeleton == reserved ||
+		skeleton
+			.split(|character: char| !character.is_alphanumeric())
+			.any(|word| word == reserved)
+}
+
+///collapses characters that look alike, so "Admin", "4dm1n" and "adm1n" are all considered the same name
+pub fn skeleton_of(name: &str) -> String {
+	name
+		.to_lowercase()
+		.chars()
+		.map(|character| match character {
+			'0'             => 'o',
+			'1' | 'i' | '|' => 'l',
+			'3'             => 'e',
+			'4' | '@'       => 'a',
+			'5' | '$'       => 's',
+			'7'             => 't',
+			_               => character
+		})
+		.collect()
+}
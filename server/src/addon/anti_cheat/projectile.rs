@@ -0,0 +1,31 @@
+use protocol::packet::common::item::Kind::Weapon;
+use protocol::packet::common::item::kind::Weapon::*;
+use protocol::packet::creature_update::equipment::Slot::{LeftWeapon, RightWeapon};
+use protocol::packet::{projectile, Projectile};
+
+use crate::addon::anti_cheat;
+use crate::addon::anti_cheat::EnsureOneOf;
+use crate::server::creature::Creature;
+use crate::server::player::Player;
+
+pub fn inspect(source: &Player, projectile: &Projectile) -> anti_cheat::Result {
+	projectile.attacker.ensure_exact(&source.id.0.unsigned_abs(), "projectile.attacker")
+}
+
+///flagged separately, as it is unverified which projectile rogues throw
+pub async fn inspect_kind(source: &Player, projectile: &Projectile) -> anti_cheat::Result {
+	let available_projectiles = projectiles_available_to(&*source.character.read().await);
+	projectile.kind.ensure_one_of(&available_projectiles, "projectile.kind")
+}
+
+fn projectiles_available_to(attacker: &Creature) -> Vec<projectile::Kind> {
+	[LeftWeapon, RightWeapon]
+		.iter()
+		.filter_map(|slot| match attacker.equipment[*slot].kind {
+			Weapon(Bow | Crossbow)          => Some(projectile::Kind::Arrow),
+			Weapon(Boomerang)               => Some(projectile::Kind::Boomerang),
+			Weapon(Staff | Wand | Bracelet) => Some(projectile::Kind::Magic),
+			_ => None
+		})
+		.collect() //todo: shurikens, presumably `Unknown`
+}
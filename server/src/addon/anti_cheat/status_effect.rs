@@ -0,0 +1,73 @@
+use std::time::Instant;
+
+use protocol::packet::creature_update::Occupation;
+use protocol::packet::creature_update::Occupation::*;
+use protocol::packet::creature_update::skill_tree::Skill;
+use protocol::packet::creature_update::skill_tree::Skill::*;
+use protocol::packet::status_effect::Kind::*;
+use protocol::packet::{status_effect, StatusEffect};
+
+use crate::addon::anti_cheat;
+use crate::addon::anti_cheat::{EnsureOneOf, EnsureWithin};
+use crate::addon::anti_cheat::hit::maximum_damage_of;
+use crate::addon::anti_cheat::violation::Failure;
+use crate::server::player::Player;
+
+///in milliseconds. skill buffs last a few seconds, growing with the points put into them; this leaves room for maxed out skills
+const MAX_DURATION: i32 = 30_000;
+///strength of the effects that don't deal damage, e.g. how much a mana shield absorbs. grows with gear, so this only catches absurd values
+const MAX_MODIFIER: f32 = 10_000.0;
+
+///which skill grants each effect to which occupation. effects granted by no skill (affection, anger, swiftness)
+///are only ever applied by the server, whose echoes are exempt by `is_echo`.
+///only logged by default (see `Check::StatusEffectSkill`)
+//todo: verify in game, especially intuition and elusiveness, then escalate the policy
+const SKILLS_GRANTING: [(status_effect::Kind, Occupation, Skill); 9] = [
+	(Bulwalk    , Warrior, Aility1 ),
+	(WarFrenzy  , Warrior, Ability2),
+	(Camouflage , Warrior, Ability3),
+	(Intuition  , Rogue  , Aility1 ),
+	(Elusiveness, Rogue  , Ability2),
+	(Poison     , Rogue  , Ability3),
+	(Intuition  , Ranger , Ability3),
+	(FireSpark  , Mage   , Aility1 ),
+	(ManaShield , Mage   , Ability2)
+];
+
+///whether the packet is just the client echoing an effect the server applied to it
+pub async fn is_echo(source: &Player, status_effect: &StatusEffect) -> bool {
+	status_effect.target == source.id &&
+		source.addon_data
+			.read().await
+			.anti_cheat_data
+			.server_applied_effects
+			.get(&status_effect.kind)
+			.is_some_and(|expiry| *expiry > Instant::now())
+}
+
+pub async fn inspect(source: &Player, status_effect: &StatusEffect) -> anti_cheat::Result {
+	status_effect.source.ensure_exact(&source.id, "status_effect.source")?;
+	status_effect.duration.ensure_within(&(0..=MAX_DURATION), "status_effect.duration")?;
+
+	let max_modifier =
+		if status_effect.kind == Poison { maximum_damage_of(&*source.character.read().await) } //damage per tick
+		else                            { MAX_MODIFIER };
+	status_effect.modifier.ensure_within(&(0.0..=max_modifier), "status_effect.modifier")
+}
+
+///whether the character has put points into a skill that grants the effect
+pub async fn inspect_skill(source: &Player, status_effect: &StatusEffect) -> anti_cheat::Result {
+	let character = source.character.read().await.clone();
+	let Some(&(.., skill)) = SKILLS_GRANTING
+		.iter()
+		.find(|(kind, occupation, _)| *kind == status_effect.kind && *occupation == character.occupation)
+		else {
+			return Err(Failure {
+				property: "status_effect.kind".to_owned(),
+				actual: format!("{:?}", status_effect.kind),
+				allowed: format!("granted to {:?}", character.occupation)
+			});
+		};
+
+	character.skill_tree[skill].ensure_within(&(1..), &format!("status_effect.skill_points.{skill:?}"))
+}
@@ -0,0 +1,215 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::{fmt, result};
+
+use crate::addon::anti_cheat;
+use crate::server::creature::Creature;
+
+use self::Action::*;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Check {
+	Movement,
+	///only logged by default, as horizontal acceleration has known false positives that haven't been investigated yet
+	Acceleration,
+	Timewarp,
+	Combat,
+	Hit,
+	Projectile,
+	///only logged by default, until the projectiles of rogues are known
+	ProjectileKind,
+	StatusEffect,
+	///only logged by default, until the skills granting each effect are verified
+	StatusEffectSkill,
+	Inventory,
+	Character,
+	///only logged by default, until the caps of each skill are confirmed
+	SkillCap
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Action {
+	LogOnly,
+	NotifyAdmins,
+	RubberBand,
+	Kick,
+	TempBan
+}
+
+///configured per check in `config.csv` as `severity;threshold:action,threshold:action,...`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Policy {
+	///how much a single violation adds to the score of its check
+	pub severity: f32,
+	///which action to take once the score reaches a threshold, in ascending order
+	pub escalation: Vec<(f32, Action)>
+}
+
+impl Check {
+	pub const ALL: [Self; 12] = [
+		Self::Movement,
+		Self::Acceleration,
+		Self::Timewarp,
+		Self::Combat,
+		Self::Hit,
+		Self::Projectile,
+		Self::ProjectileKind,
+		Self::StatusEffect,
+		Self::StatusEffectSkill,
+		Self::Inventory,
+		Self::Character,
+		Self::SkillCap
+	];
+
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Movement          => "movement",
+			Self::Acceleration      => "acceleration",
+			Self::Timewarp          => "timewarp",
+			Self::Combat            => "combat",
+			Self::Hit               => "hit",
+			Self::Projectile        => "projectile",
+			Self::ProjectileKind    => "projectile_kind",
+			Self::StatusEffect      => "status_effect",
+			Self::StatusEffectSkill => "status_effect_skill",
+			Self::Inventory         => "inventory",
+			Self::Character         => "character",
+			Self::SkillCap          => "skill_cap"
+		}
+	}
+
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::ALL
+			.into_iter()
+			.find(|check| check.name() == name)
+	}
+}
+
+impl Display for Check {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+impl Action {
+	pub const ALL: [Self; 5] = [
+		Self::LogOnly,
+		Self::NotifyAdmins,
+		Self::RubberBand,
+		Self::Kick,
+		Self::TempBan
+	];
+
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::LogOnly      => "log_only",
+			Self::NotifyAdmins => "notify_admins",
+			Self::RubberBand   => "rubber_band",
+			Self::Kick         => "kick",
+			Self::TempBan      => "temp_ban"
+		}
+	}
+
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::ALL
+			.into_iter()
+			.find(|action| action.name() == name)
+	}
+}
+
+impl Policy {
+	pub fn action_at(&self, score: f32) -> Action {
+		self.escalation
+			.iter()
+			.rev()
+			.find(|(threshold, _)| score >= *threshold)
+			.map_or(LogOnly, |(_, action)| *action)
+	}
+}
+
+impl FromStr for Policy {
+	type Err = String;
+
+	fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+		let (severity, escalation) = value
+			.split_once(';')
+			.ok_or("expected severity;escalation")?;
+
+		let severity = severity
+			.trim()
+			.parse()
+			.map_err(|_| format!("invalid severity {severity}"))?;
+
+		let mut escalation = escalation
+			.split(',')
+			.filter(|step| !step.trim().is_empty())
+			.map(|step| {
+				let (threshold, action) = step
+					.split_once(':')
+					.ok_or_else(|| format!("expected threshold:action, got {step}"))?;
+				let threshold = threshold
+					.trim()
+					.parse::<f32>()
+					.map_err(|_| format!("invalid threshold {threshold}"))?;
+				let action = Action::from_name(action.trim())
+					.ok_or_else(|| format!("unknown action {action}"))?;
+
+				Ok((threshold, action))
+			})
+			.collect::<result::Result<Vec<_>, String>>()?;
+		escalation.sort_by(|(lhs, _), (rhs, _)| lhs.total_cmp(rhs));
+
+		Ok(Self { severity, escalation })
+	}
+}
+
+///what an inspection took offense at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+	pub property: String,
+	///empty if the failure isn't about a single value
+	pub actual: String,
+	pub allowed: String
+}
+
+impl From<&str> for Failure {
+	fn from(description: &str) -> Self {
+		Self {
+			property: description.to_owned(),
+			actual: String::new(),
+			allowed: String::new()
+		}
+	}
+}
+
+impl Display for Failure {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		if self.actual.is_empty() {
+			return f.write_str(&self.property);
+		}
+		write!(f, "{} was {}, allowed was {}", self.property, self.actual, self.allowed)
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Violation {
+	pub check: Check,
+	pub failure: Failure,
+	///the character before and after applying the offending packet, if one was involved
+	pub states: Option<Box<(Creature, Creature)>>
+}
+
+pub trait Flag {
+	///attributes a failed inspection to `check`
+	fn flag(self, check: Check) -> result::Result<(), Violation>;
+}
+
+impl Flag for anti_cheat::Result {
+	fn flag(self, check: Check) -> result::Result<(), Violation> {
+		self.map_err(|failure| Violation {
+			check,
+			failure,
+			states: None
+		})
+	}
+}
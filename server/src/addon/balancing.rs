@@ -68,6 +68,7 @@ impl AirTimeTracker {
 				duration: 2000,
 				creature_id3: source.id,//todo: is this needed?
 			};
+			source.addon_data.write().await.anti_cheat_data.expect_echo(&anger);
 			let world_update = WorldUpdate {
 				status_effects: vec![anger],
 				sounds: vec![Sound::at(position, Magic01)],
@@ -103,9 +104,12 @@ pub async fn buff_warfrenzy(warfrenzy: &StatusEffect, server: &Server) {
 		kind: Swiftness,
 		..*warfrenzy
 	};
+	if let Some(target) = server.find_player_by_id(warfrenzy.target).await {
+		target.addon_data.write().await.anti_cheat_data.expect_echo(&swiftness);
+	}
 	// sending this separately from the original status effect
 	// as that one isn't sent back to the source
-	server.broadcast(&WorldUpdate::from(swiftness), None).await;
+	server.broadcast_batched(&WorldUpdate::from(swiftness), None).await;
 }
 
 const GLOBAL_DAMAGE_MULTIPLIER: f32 = 0.5;
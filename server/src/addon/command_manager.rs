@@ -57,6 +57,17 @@ impl Default for CommandManager {
 			cm.register(Team);
 			cm.register(Act);
 			cm.register(Heal);
+			cm.register(Ac);
+			cm.register(Stats);
+			cm.register(Match);
+			cm.register(Join);
+			cm.register(Duel);
+			cm.register(Rank);
+			cm.register(Top);
+			cm.register(Tournament);
+			cm.register(Bracket);
+			cm.register(Region);
+			cm.register(Spectate);
 		})
 	}
 }
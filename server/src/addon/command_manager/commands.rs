@@ -15,6 +15,17 @@ mod test;
 mod team;
 mod act;
 mod heal;
+mod ac;
+mod stats;
+mod matches;
+mod join;
+mod duel;
+mod rank;
+mod top;
+mod tournament;
+mod bracket;
+mod region;
+mod spectate;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
 pub struct Who;
@@ -55,4 +66,37 @@ pub struct Team;
 pub struct Act;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
-pub struct Heal;
\ No newline at end of file
+pub struct Heal;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Ac;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Stats;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Match;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Join;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Duel;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Rank;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Top;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Tournament;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Bracket;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Region;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Spectate;
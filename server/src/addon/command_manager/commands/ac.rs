@@ -0,0 +1,78 @@
+use std::str::SplitWhitespace;
+
+use tap::Pipe;
+
+use crate::addon::anti_cheat::violation::Check;
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Ac;
+use crate::server::player::Player;
+use crate::server::Server;
+
+const LIST_SIZE: usize = 5;
+
+impl Command for Ac {
+	const LITERAL: &'static str = "ac";
+	const ADMIN_ONLY: bool = true;
+
+	async fn execute<'fut>(&'fut self, server: &'fut Server, _caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		match params.next() {
+			Some("shadow") => shadow(server, params).await,
+			Some("top") => top(server).await,
+			Some(player) => history(server, player).await,
+			None => Err("usage: /ac <player|top|shadow [check]>")
+		}
+	}
+}
+
+async fn shadow(server: &Server, params: &mut SplitWhitespace<'_>) -> CommandResult {
+	let enforcement = &server.addons.anti_cheat;
+
+	let Some(check_name) = params.next()
+		else {
+			let shadowed_checks = enforcement
+				.shadowed_checks()
+				.await
+				.into_iter()
+				.map(Check::name)
+				.collect::<Vec<_>>()
+				.join(", ");
+			let all_checks = Check::ALL.map(Check::name).join(", ");
+
+			return Ok(Some(format!("shadowed: [{shadowed_checks}] available: [{all_checks}]")));
+		};
+
+	let check = Check::from_name(check_name).ok_or("unknown check")?;
+	let shadowed = enforcement.toggle_shadow_mode(check).await;
+
+	Ok(Some(format!("{check} check is {} now", if shadowed { "shadowed" } else { "enforced" })))
+}
+
+async fn history(server: &Server, player: &str) -> CommandResult {
+	let violations = server.addons.anti_cheat.audit_log
+		.recent_violations_of(player, LIST_SIZE)
+		.await;
+
+	if violations.is_empty() {
+		return Ok(Some(format!("no recent violations by {player}")));
+	}
+
+	Ok(Some(violations.join(" | ")))
+}
+
+async fn top(server: &Server) -> CommandResult {
+	let offenders = server.addons.anti_cheat.audit_log
+		.worst_offenders(LIST_SIZE)
+		.await;
+
+	if offenders.is_empty() {
+		return Ok(Some("no recent violations".to_owned()));
+	}
+
+	offenders
+		.into_iter()
+		.map(|(name, severity)| format!("{name}: {severity:.1}"))
+		.collect::<Vec<_>>()
+		.join(", ")
+		.pipe(Some)
+		.pipe(Ok)
+}
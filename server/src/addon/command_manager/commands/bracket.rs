@@ -0,0 +1,34 @@
+use std::str::SplitWhitespace;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Bracket;
+use crate::addon::pvp::tournaments::Format;
+use crate::server::player::Player;
+use crate::server::Server;
+
+impl Command for Bracket {
+	const LITERAL: &'static str = "bracket";
+	const ADMIN_ONLY: bool = true;
+
+	async fn execute<'fut>(&'fut self, server: &'fut Server, _caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let tournament = &server.addons.tournaments;
+
+		match params.next() {
+			Some("open") => {
+				let format = params
+					.next()
+					.map_or(Some(Format::SingleElimination), Format::from_name)
+					.ok_or("usage: /bracket open [single|double]")?;
+				tournament.open(server, format).await.map(|()| None)
+			}
+			Some("start") => tournament.start(server).await.map(|()| None),
+			Some("call") => tournament.call(server).await.map(|_| None),
+			Some("win") => {
+				let winner = params.next().ok_or("usage: /bracket win <player>")?;
+				tournament.record_walkover(server, winner).await.map(|()| None)
+			}
+			Some("cancel") => tournament.cancel(server).await.map(|()| None),
+			_ => Err("usage: /bracket <open [single|double]|start|call|win <player>|cancel>")
+		}
+	}
+}
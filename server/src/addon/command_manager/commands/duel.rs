@@ -0,0 +1,34 @@
+use std::str::SplitWhitespace;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Duel;
+use crate::addon::command_manager::utils::INGAME_ONLY;
+use crate::server::player::Player;
+use crate::server::Server;
+
+impl Command for Duel {
+	const LITERAL: &'static str = "duel";
+	const ADMIN_ONLY: bool = false;
+
+	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = caller.ok_or(INGAME_ONLY)?;
+		let duels = &server.addons.duels;
+
+		match params.next() {
+			None => Err("usage: /duel <player> [best of] [arena] | /duel <accept|decline>"),
+			Some("accept") => duels.accept(server, caller).await.map(|()| None),
+			Some("decline") => duels.decline(server, caller).await.map(|()| None),
+			Some(query) => {
+				let target = server.find_player(query).await.ok_or("target not found")?;
+				let best_of = params
+					.next()
+					.map_or(Ok(1), str::parse)
+					.map_err(|_| "invalid number of rounds")?;
+				let arena = params.next() == Some("arena");
+
+				duels.challenge(caller, &target, best_of, arena).await?;
+				Ok(Some("challenge sent".to_owned()))
+			}
+		}
+	}
+}
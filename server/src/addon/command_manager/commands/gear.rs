@@ -27,6 +27,12 @@ impl Command for Gear {
 		let items = create_items(character.occupation, character.level as i16);
 		drop(character);
 
+		let mut addon_data = caller.addon_data.write().await;
+		for item in &items {
+			addon_data.anti_cheat_data.receive_item(item.clone());
+		}
+		drop(addon_data);
+
 		let pickups: Vec<_> = items
 			.into_iter()
 			.map(|item| Pickup {
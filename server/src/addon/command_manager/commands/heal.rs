@@ -1,12 +1,11 @@
 use std::str::SplitWhitespace;
-use protocol::packet::{Hit, WorldUpdate};
-use protocol::packet::hit::Kind::Normal;
 
 use crate::addon::command_manager::{Command, CommandResult};
 use crate::addon::command_manager::commands::Heal;
 use crate::addon::command_manager::utils::INGAME_ONLY;
 use crate::server::player::Player;
 use crate::server::Server;
+use crate::server::utils::heal;
 
 impl Command for Heal {
 	const LITERAL: &'static str = "heal";
@@ -14,25 +13,14 @@ impl Command for Heal {
 
 	async fn execute<'fut>(&'fut self, _server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
 		let caller = caller.ok_or(INGAME_ONLY)?;
-		let character = caller.character.read().await;
 
 		let mut amount: f32 = 9999.0;
 		if let Some(str) = params.next() {
 			amount = str.parse().map_err(|_| "invalid amount specified")?;
 		};
 
-		let heal = Hit {
-			attacker: caller.id,
-			target: caller.id,
-			damage: -amount,
-			position: character.position,
-			kind: Normal,
-			..Default::default()
-		};
-		drop(character);
-
-		caller.send_ignoring(&WorldUpdate::from(heal)).await;
+		heal(caller, amount).await;
 
 		Ok(None)
 	}
-}
\ No newline at end of file
+}
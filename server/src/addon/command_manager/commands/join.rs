@@ -0,0 +1,28 @@
+use std::str::SplitWhitespace;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Join;
+use crate::addon::command_manager::utils::INGAME_ONLY;
+use crate::server::player::Player;
+use crate::server::Server;
+
+impl Command for Join {
+	const LITERAL: &'static str = "join";
+	const ADMIN_ONLY: bool = false;
+
+	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = caller.ok_or(INGAME_ONLY)?;
+
+		match params.next() {
+			None => {
+				server.addons.matches.join(caller).await?;
+				Ok(Some("joined the match".to_owned()))
+			}
+			Some("leave") => {
+				server.addons.matches.on_leave(server, caller).await;
+				Ok(Some("left the match".to_owned()))
+			}
+			Some(_) => Err("usage: /join [leave]")
+		}
+	}
+}
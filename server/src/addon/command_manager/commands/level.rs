@@ -13,7 +13,7 @@ impl Command for Level {
 	const LITERAL: &'static str = "level";
 	const ADMIN_ONLY: bool = false;
 
-	async fn execute<'fut>(&'fut self, _server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
 		let caller = caller.ok_or(INGAME_ONLY)?;
 
 		let target_level: i32 = params
@@ -38,7 +38,7 @@ impl Command for Level {
 
 		drop(character);
 
-		give_xp(caller, xp).await;
+		give_xp(server, caller, xp).await;
 
 		Ok(None)
 	}
@@ -0,0 +1,26 @@
+use std::str::SplitWhitespace;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Match;
+use crate::addon::pvp::matches::mode::Mode;
+use crate::server::player::Player;
+use crate::server::Server;
+
+impl Command for Match {
+	const LITERAL: &'static str = "match";
+	const ADMIN_ONLY: bool = true;
+
+	async fn execute<'fut>(&'fut self, server: &'fut Server, _caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let matches = &server.addons.matches;
+
+		match params.next() {
+			None => Ok(Some(matches.status(server).await)),
+			Some("begin") => matches.begin(server).await.map(|()| None),
+			Some("stop") => matches.cancel(server).await.map(|()| None),
+			Some(mode_name) => {
+				let mode = Mode::from_name(mode_name).ok_or("usage: /match <dm|tdm|lts|ctf|begin|stop>")?;
+				matches.open(server, mode).await.map(|()| None)
+			}
+		}
+	}
+}
@@ -0,0 +1,35 @@
+use std::str::SplitWhitespace;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Rank;
+use crate::addon::command_manager::utils::INGAME_ONLY;
+use crate::server::player::Player;
+use crate::server::Server;
+
+impl Command for Rank {
+	const LITERAL: &'static str = "rank";
+	const ADMIN_ONLY: bool = false;
+
+	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let name = match params.next() {
+			Some(query) => match server.find_player(query).await {
+				Some(target) => target.character.read().await.name.clone(),
+				None => query.to_owned() //ratings outlive sessions, so offline players can be looked up by their exact name
+			},
+			None => caller.ok_or(INGAME_ONLY)?.character.read().await.name.clone()
+		};
+
+		let ratings = server.addons.ratings.of(&name).await;
+		if ratings.is_empty() {
+			return Ok(Some(format!("{name} is unrated")));
+		}
+
+		let summary = ratings
+			.into_iter()
+			.map(|(mode, class, rating)| format!("{mode} {class} {:.0} ({} games)", rating.value, rating.games))
+			.collect::<Vec<_>>()
+			.join(", ");
+
+		Ok(Some(format!("{name}: {summary}")))
+	}
+}
@@ -0,0 +1,53 @@
+use std::str::SplitWhitespace;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Region;
+use crate::addon::command_manager::utils::INGAME_ONLY;
+use crate::server::player::Player;
+use crate::server::Server;
+
+const DEFAULT_RADIUS: i64 = 32;
+
+impl Command for Region {
+	const LITERAL: &'static str = "region";
+	const ADMIN_ONLY: bool = true;
+
+	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let regions = &server.addons.safe_zones;
+
+		match params.next() {
+			None => {
+				let names = regions.names().await;
+				if names.is_empty() {
+					return Ok(Some("there are no safe zones".to_owned()));
+				}
+				Ok(Some(names.join(", ")))
+			}
+			Some("add") => {
+				let name = params.next().ok_or("usage: /region add <name> [radius in blocks]")?;
+				let radius = params
+					.next()
+					.map_or(Ok(DEFAULT_RADIUS), str::parse)
+					.map_err(|_| "invalid radius")?;
+				let position = caller.ok_or(INGAME_ONLY)?.character.read().await.position;
+
+				regions.add_around(name, position, radius).await?;
+				Ok(Some(format!("added {name}")))
+			}
+			Some("zone") => {
+				let name = params.next().ok_or("usage: /region zone <name>")?;
+				let position = caller.ok_or(INGAME_ONLY)?.character.read().await.position;
+
+				regions.add_zone(name, position).await?;
+				Ok(Some(format!("added {name}")))
+			}
+			Some("remove") => {
+				let name = params.next().ok_or("usage: /region remove <name>")?;
+
+				regions.remove(name).await?;
+				Ok(Some(format!("removed {name}")))
+			}
+			Some(_) => Err("usage: /region [add <name> [radius]|zone <name>|remove <name>]")
+		}
+	}
+}
@@ -0,0 +1,34 @@
+use std::str::SplitWhitespace;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Spectate;
+use crate::addon::command_manager::utils::INGAME_ONLY;
+use crate::addon::spectator;
+use crate::server::player::Player;
+use crate::server::Server;
+
+impl Command for Spectate {
+	const LITERAL: &'static str = "spectate";
+	const ADMIN_ONLY: bool = true;
+
+	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let caller = caller.ok_or(INGAME_ONLY)?;
+
+		match params.next() {
+			None => {
+				let target_id = spectator::target_of(caller).await.ok_or("usage: /spectate <player|stop>")?;
+				let target = server.find_player_by_id(target_id).await.ok_or("target not found")?;
+				Ok(Some(format!("spectating {}", target.character.read().await.name)))
+			}
+			Some("stop") => {
+				spectator::stop(server, caller).await?;
+				Ok(Some("stopped spectating".to_owned()))
+			}
+			Some(query) => {
+				let target = server.find_player(query).await.ok_or("target not found")?;
+				spectator::start(server, caller, &target).await?;
+				Ok(Some(format!("spectating {}", target.character.read().await.name)))
+			}
+		}
+	}
+}
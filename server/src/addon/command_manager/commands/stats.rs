@@ -0,0 +1,38 @@
+use std::str::SplitWhitespace;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Stats;
+use crate::addon::command_manager::utils::INGAME_ONLY;
+use crate::server::player::Player;
+use crate::server::Server;
+
+impl Command for Stats {
+	const LITERAL: &'static str = "stats";
+	const ADMIN_ONLY: bool = false;
+
+	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let found_player;
+		let target = match params.next() {
+			Some(query) => {
+				found_player = server.find_player(query).await.ok_or("target not found")?;
+				&found_player
+			}
+			None => caller.ok_or(INGAME_ONLY)?
+		};
+
+		let name = target.character.read().await.name.clone();
+		let addon_data = target.addon_data.read().await;
+		let kill_data = &addon_data.kill_data;
+
+		let message = format!(
+			"{name}: {} kills, {} deaths, {} assists, {:.0} damage dealt",
+			kill_data.kills,
+			kill_data.deaths,
+			kill_data.assists,
+			kill_data.damage_dealt
+		);
+		drop(addon_data);
+
+		Ok(Some(message))
+	}
+}
@@ -0,0 +1,30 @@
+use std::str::SplitWhitespace;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Top;
+use crate::addon::pvp::ratings::{DUEL, format_leaderboard, LEADERBOARD_SIZE, modes, parse_class};
+use crate::server::player::Player;
+use crate::server::Server;
+
+impl Command for Top {
+	const LITERAL: &'static str = "top";
+	const ADMIN_ONLY: bool = false;
+
+	async fn execute<'fut>(&'fut self, server: &'fut Server, _caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let mode = params.next().unwrap_or(DUEL);
+		if !modes().any(|known_mode| known_mode == mode) {
+			return Err("usage: /top [duel|dm|tdm|lts|ctf] [class]");
+		}
+		let class = params
+			.next()
+			.map(|name| parse_class(name).ok_or("unknown class"))
+			.transpose()?;
+
+		let top = server.addons.ratings.top(mode, class, LEADERBOARD_SIZE).await;
+		if top.is_empty() {
+			return Ok(Some("nobody is rated yet".to_owned()));
+		}
+
+		Ok(Some(format_leaderboard(&top).join(", ")))
+	}
+}
@@ -0,0 +1,29 @@
+use std::str::SplitWhitespace;
+
+use crate::addon::command_manager::{Command, CommandResult};
+use crate::addon::command_manager::commands::Tournament;
+use crate::addon::command_manager::utils::INGAME_ONLY;
+use crate::server::player::Player;
+use crate::server::Server;
+
+impl Command for Tournament {
+	const LITERAL: &'static str = "tournament";
+	const ADMIN_ONLY: bool = false;
+
+	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+		let tournament = &server.addons.tournaments;
+
+		match params.next() {
+			None => Ok(Some(tournament.status().await)),
+			Some("join") => {
+				tournament.join(caller.ok_or(INGAME_ONLY)?).await?;
+				Ok(Some("signed up".to_owned()))
+			}
+			Some("leave") => {
+				tournament.leave(caller.ok_or(INGAME_ONLY)?).await?;
+				Ok(Some("signed off".to_owned()))
+			}
+			Some(_) => Err("usage: /tournament [join|leave]")
+		}
+	}
+}
@@ -11,7 +11,7 @@ impl Command for Xp {
 	const LITERAL: &'static str = "xp";
 	const ADMIN_ONLY: bool = false;
 
-	async fn execute<'fut>(&'fut self, _server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
+	async fn execute<'fut>(&'fut self, server: &'fut Server, caller: Option<&'fut Player>, params: &'fut mut SplitWhitespace<'fut>) -> CommandResult {
 		let caller = caller.ok_or(INGAME_ONLY)?;
 
 		let amount: i32 = params
@@ -20,7 +20,7 @@ impl Command for Xp {
 			.parse()
 			.map_err(|_| "invalid amount specified")?;
 
-		give_xp(caller, amount).await;
+		give_xp(server, caller, amount).await;
 
 		Ok(None)
 	}
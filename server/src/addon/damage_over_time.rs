@@ -0,0 +1,73 @@
+use std::time::{Duration, Instant};
+
+use tokio::sync::RwLock;
+
+use protocol::packet::{Hit, WorldUpdate};
+use protocol::packet::world_update::Sound;
+use protocol::packet::world_update::sound::Kind::SlimeGroan;
+
+use crate::addon::pvp::kill_tracker;
+use crate::server::Server;
+
+const INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Default)]
+pub struct PoisonTracker {
+	poisonings: RwLock<Vec<Poisoning>>
+}
+
+#[derive(Debug)]
+struct Poisoning {
+	hit: Hit,
+	remaining_ticks: i32,
+	next_tick: Instant
+}
+
+impl PoisonTracker {
+	///`hit` is dealt every 500ms until `duration` runs out or the target dies
+	pub async fn apply(&self, hit: Hit, duration: i32) {
+		self.poisonings.write().await.push(Poisoning {
+			hit,
+			remaining_ticks: (duration / INTERVAL.as_millis() as i32).max(1),
+			next_tick: Instant::now()
+		});
+	}
+
+	pub async fn on_tick(&self, server: &Server) {
+		let now = Instant::now();
+		let mut poisonings = self.poisonings.write().await;
+
+		for poisoning in poisonings.iter_mut() {
+			if poisoning.next_tick > now {
+				continue;
+			}
+
+			let Some(target) = server.find_player_by_id(poisoning.hit.target).await
+				else {
+					poisoning.remaining_ticks = 0; //disconnected
+					continue;
+				};
+
+			if target.character.read().await.health == 0.0 {
+				poisoning.remaining_ticks = 0;
+				continue;
+			}
+
+			if let Some(attacker) = server.find_player_by_id(poisoning.hit.attacker).await {
+				kill_tracker::record_attack(&attacker, &target, poisoning.hit.damage, "poison".to_owned()).await;
+			}
+
+			let world_update = WorldUpdate {
+				sounds: vec![Sound::at(poisoning.hit.position, SlimeGroan)],
+				hits: vec![poisoning.hit.clone()],
+				..Default::default()
+			};
+			target.enqueue(&world_update).await;
+
+			poisoning.remaining_ticks -= 1;
+			poisoning.next_tick += INTERVAL;
+		}
+
+		poisonings.retain(|poisoning| poisoning.remaining_ticks > 0);
+	}
+}
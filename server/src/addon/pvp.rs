@@ -1,16 +1,30 @@
-use std::ptr;
+use std::future::join;
+
 use futures::future::join_all;
-use tap::Pipe;
+use tap::{Pipe, Tap};
 use protocol::packet::creature_update::Affiliation;
 use protocol::packet::CreatureUpdate;
 
 pub mod team;
 pub mod map_head;
+pub mod kill_tracker;
+pub mod rewards;
+pub mod matches;
+pub mod duels;
+pub mod ratings;
+pub mod tournaments;
+pub mod safe_zones;
 
+use crate::addon::spectator;
+use crate::server::despawn;
 use crate::server::player::Player;
 use crate::server::Server;
 
 pub async fn on_creature_update(server: &Server, source: &Player, packet: &CreatureUpdate) {
+	if spectator::is_spectating(source).await {
+		return; //not part of the game until they stop
+	}
+
 	let team_members =
 		if let Some(target_team) = source.addon_data.read().await.team {
 			team::get_members(server, target_team).await
@@ -19,40 +33,96 @@ pub async fn on_creature_update(server: &Server, source: &Player, packet: &Creat
 		};
 	team::display::update_for_all_members(packet, source, &team_members).await;
 	map_head::update(server, source, packet, &team_members).await;
+	safe_zones::on_creature_update(server, source, packet).await;
+	server.addons.matches.on_creature_update(server, source, packet).await;
 }
 
-pub async fn broadcast(server: &Server, source: &Player, packet: &CreatureUpdate) -> bool {
+///whether `viewer` may attack `subject`. duelists only fight each other, everyone else fights everyone outside their team unless one of them is in a safe zone or eliminated
+pub async fn is_hostile(viewer: &Player, subject: &Player) -> bool {
+	let viewer_data = viewer.addon_data.read().await;
+	let (own_team, own_duel, own_safety) = (viewer_data.team, viewer_data.duel, viewer_data.safe_zone.is_some() || viewer_data.eliminated);
+	drop(viewer_data);
+	let subject_data = subject.addon_data.read().await;
+	let (other_team, other_duel, other_safety) = (subject_data.team, subject_data.duel, subject_data.safe_zone.is_some() || subject_data.eliminated);
+	drop(subject_data);
+
+	match (own_duel, other_duel) {
+		(Some(engagement), _) if engagement.opponent == subject.id => engagement.fighting,
+		(Some(_), _) | (_, Some(_)) => false,
+		_ if own_safety || other_safety => false,
+		_ => own_team.is_none() || own_team != other_team
+	}
+}
+
+///makes `subject` show up as an enemy for `viewer`, if they are hostile
+pub async fn adapt_for(viewer: &Player, subject: &Player, packet: &mut CreatureUpdate) {
 	if packet.affiliation.is_none() && packet.rarity.is_none() {//if packet.flags.is_none() {
-		return false;
+		return;
 	};
 
-	let mut pvp_enabled_packet = packet.clone();
+	if !is_hostile(viewer, subject).await {
+		return;
+	}
 
-	if let Some(ref mut affiliation) = pvp_enabled_packet.affiliation {
+	if let Some(ref mut affiliation) = packet.affiliation {
 		*affiliation = Affiliation::Enemy;
 	}
 
-	if let Some(ref mut rarity) = pvp_enabled_packet.rarity {
+	if let Some(ref mut rarity) = packet.rarity {
 		*rarity = 4;
 	}
+}
+
+///the full state of `subject`, as `viewer` is supposed to see it
+pub async fn snapshot(viewer: &Player, subject: &Player) -> CreatureUpdate {
+	if spectator::is_spectating(subject).await {
+		return despawn(subject.id);
+	}
+	let hostile = is_hostile(viewer, subject).await;
 
-	let own_team = source.addon_data.read().await.team;
+	subject
+		.character
+		.read()
+		.await
+		.to_update(subject.id)
+		.tap_mut(|packet| apply_hostility(packet, hostile))
+}
 
+///resends how `player` and everyone around them see each other, after [`is_hostile`] changed for them
+pub async fn refresh_hostility(server: &Server, player: &Player) {
 	server
 		.players
 		.read()
 		.await
 		.iter()
-		.filter(|target| !ptr::eq(target.as_ref(), source))
-		.map(|target| async {
-			let other_team = target.addon_data.read().await.team;
-			let is_teammate = own_team.is_some() && own_team == other_team;
-
-			let packet_to_send = if is_teammate { packet } else { &pvp_enabled_packet };
-			target.send_ignoring(packet_to_send).await;
+		.filter(|other_player| other_player.id != player.id)
+		.map(|other_player| async {
+			join!(
+				send_hostility(other_player, player),
+				send_hostility(player, other_player)
+			).await;
 		})
 		.pipe(join_all)
 		.await;
+}
 
-	true
-}
\ No newline at end of file
+async fn send_hostility(viewer: &Player, subject: &Player) {
+	if !viewer.addon_data.read().await.replicated_creatures.contains_key(&subject.id) {
+		return; //a full snapshot gets sent once they come into range
+	}
+	if spectator::is_spectating(subject).await {
+		return; //would reveal them
+	}
+
+	let hostile = is_hostile(viewer, subject).await;
+	let update = CreatureUpdate {
+		id: subject.id,
+		..Default::default()
+	}.tap_mut(|packet| apply_hostility(packet, hostile));
+	viewer.enqueue(&update).await;
+}
+
+const fn apply_hostility(packet: &mut CreatureUpdate, hostile: bool) {
+	packet.affiliation = Some(if hostile { Affiliation::Enemy } else { Affiliation::Player });
+	packet.rarity = Some(if hostile { 4 } else { 0 });
+}
@@ -0,0 +1,316 @@
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::time::{Duration, Instant};
+
+use tap::Tap;
+use tokio::sync::RwLock;
+
+use protocol::nalgebra::Point3;
+use protocol::packet::common::CreatureId;
+use protocol::utils::constants::CombatClass;
+
+use crate::addon::pvp::refresh_hostility;
+use crate::addon::spectator;
+use crate::addon::pvp::ratings::{DUEL, Placement};
+use crate::server::player::Player;
+use crate::server::Server;
+use crate::server::utils::heal;
+
+const CHALLENGE_TIMEOUT: Duration = Duration::from_secs(60);
+const COUNTDOWN: Duration = Duration::from_secs(3);
+pub const MAX_BEST_OF: u32 = 9;
+const FULL_HEAL: f32 = 9999.0;
+
+///kept in the [`AddonData`](crate::server::player::addon_data::AddonData) of both duelists, see [`pvp::is_hostile`](crate::addon::pvp::is_hostile)
+#[derive(Debug, Clone, Copy)]
+pub struct Engagement {
+	pub opponent: CreatureId,
+	///false during the countdown before each round
+	pub fighting: bool
+}
+
+#[derive(Debug)]
+struct Challenge {
+	challenger: CreatureId,
+	challenged: CreatureId,
+	best_of: u32,
+	arena: bool,
+	issued: Instant
+}
+
+#[derive(Debug)]
+struct Duel {
+	duelists: [CreatureId; 2],
+	///remembered so the result can still be announced and rated if one of them leaves
+	names: [String; 2],
+	classes: [CombatClass; 2],
+	wins: [u32; 2],
+	best_of: u32,
+	arena: bool,
+	///when the current countdown ends and the last second that was announced, `None` while fighting
+	countdown: Option<(Instant, u64)>
+}
+
+#[derive(Debug)]
+pub struct DuelManager {
+	challenges: RwLock<Vec<Challenge>>,
+	duels: RwLock<Vec<Duel>>,
+	///where each duelist gets teleported to if the duel takes place in the arena
+	arena: [Point3<i64>; 2]
+}
+
+impl Default for DuelManager {
+	fn default() -> Self {
+		let file_content = match fs::read_to_string(Self::FILE_PATH) {
+			Ok(content) => content,
+
+			Err(error) if error.kind() == NotFound => {
+				concat!(0x801F800000,';',0x8020800000,';',0,'\n',0x8021800000,';',0x8020800000,';',0)
+					.tap(|content| fs::write(Self::FILE_PATH, content).unwrap())
+					.to_owned()
+			}
+
+			Err(error) => panic!("failed to load {} - {}", Self::FILE_PATH, error)
+		};
+
+		Self {
+			challenges: RwLock::default(),
+			duels: RwLock::default(),
+			arena: file_content
+				.lines()
+				.map(|line| {
+					let splits: [&str; 3] = line
+						.split(';')
+						.collect::<Vec<_>>()
+						.try_into()
+						.unwrap();
+
+					Point3::new(
+						splits[0].parse().unwrap(),
+						splits[1].parse().unwrap(),
+						splits[2].parse().unwrap()
+					)
+				})
+				.collect::<Vec<_>>()
+				.try_into()
+				.unwrap()
+		}
+	}
+}
+
+impl DuelManager {
+	const FILE_PATH: &'static str = "duel_arena.csv";
+
+	pub async fn challenge(&self, challenger: &Player, target: &Player, best_of: u32, arena: bool) -> Result<(), &'static str> {
+		if challenger.id == target.id {
+			return Err("you can't duel yourself");
+		}
+		if best_of % 2 == 0 || best_of > MAX_BEST_OF {
+			return Err("best of must be an odd number up to 9");
+		}
+		if challenger.addon_data.read().await.duel.is_some() || target.addon_data.read().await.duel.is_some() {
+			return Err("one of you is in a duel already");
+		}
+		if spectator::is_spectating(challenger).await || spectator::is_spectating(target).await {
+			return Err("one of you is spectating");
+		}
+
+		let mut pending = self.challenges.write().await;
+		pending.retain(|challenge| challenge.challenger != challenger.id);
+		pending.push(Challenge {
+			challenger: challenger.id,
+			challenged: target.id,
+			best_of,
+			arena,
+			issued: Instant::now()
+		});
+		drop(pending);
+
+		let location = if arena { ", in the arena" } else { "" };
+		target.notify(format!(
+			"{} challenges you to a duel (best of {best_of}{location}) - /duel accept or /duel decline",
+			challenger.character.read().await.name
+		)).await;
+		Ok(())
+	}
+
+	pub async fn accept(&self, server: &Server, player: &Player) -> Result<(), &'static str> {
+		let challenge = self.take_challenge_of(player).await.ok_or("nobody challenged you")?;
+		let challenger = server.find_player_by_id(challenge.challenger).await.ok_or("your challenger left")?;
+		self.start(server, &challenger, player, challenge.best_of, challenge.arena).await
+	}
+
+	///starts a duel right away, without a challenge
+	#[expect(clippy::significant_drop_tightening, reason = "concurrent starts must not both pass the check")]
+	pub async fn start(&self, server: &Server, first: &Player, second: &Player, best_of: u32, arena: bool) -> Result<(), &'static str> {
+		let mut duels = self.duels.write().await;
+		if first.addon_data.read().await.duel.is_some() || second.addon_data.read().await.duel.is_some() {
+			return Err("one of them is in a duel already");
+		}
+
+		for (duelist, opponent) in [(first, second), (second, first)] {
+			duelist.addon_data.write().await.duel = Some(Engagement { opponent: opponent.id, fighting: false });
+			refresh_hostility(server, duelist).await;
+		}
+
+		let (first_name, first_class) = identity_of(first).await;
+		let (second_name, second_class) = identity_of(second).await;
+		let mut duel = Duel {
+			duelists: [first.id, second.id],
+			names: [first_name, second_name],
+			classes: [first_class, second_class],
+			wins: [0, 0],
+			best_of,
+			arena,
+			countdown: None
+		};
+		self.start_round(server, &mut duel);
+		duels.push(duel);
+		Ok(())
+	}
+
+	pub async fn decline(&self, server: &Server, player: &Player) -> Result<(), &'static str> {
+		let challenge = self.take_challenge_of(player).await.ok_or("nobody challenged you")?;
+		if let Some(challenger) = server.find_player_by_id(challenge.challenger).await {
+			challenger.notify(format!("{} declined your duel", player.character.read().await.name)).await;
+		}
+		Ok(())
+	}
+
+	pub async fn on_tick(&self, server: &Server) {
+		self.challenges.write().await.retain(|challenge| challenge.issued.elapsed() < CHALLENGE_TIMEOUT);
+
+		let now = Instant::now();
+		for duel in self.duels.write().await.iter_mut() {
+			let Some((ends, last_announced)) = duel.countdown
+				else { continue; };
+
+			if now >= ends {
+				duel.countdown = None;
+				for (index, id) in duel.duelists.into_iter().enumerate() {
+					let Some(duelist) = server.find_player_by_id(id).await
+						else { continue; };
+
+					duelist.addon_data.write().await.duel = Some(Engagement { opponent: duel.duelists[1 - index], fighting: true });
+					refresh_hostility(server, &duelist).await;
+					duelist.notify("fight!").await;
+				}
+				continue;
+			}
+
+			let remaining = (ends - now).as_secs() + 1;
+			if remaining < last_announced {
+				duel.countdown = Some((ends, remaining));
+				notify_both(server, duel, remaining.to_string()).await;
+			}
+		}
+	}
+
+	pub async fn on_death(&self, server: &Server, victim: &Player) {
+		let mut duels = self.duels.write().await;
+		let Some(index) = duels
+			.iter()
+			.position(|duel| duel.countdown.is_none() && duel.duelists.contains(&victim.id))
+			else { return; };
+
+		let duel = &mut duels[index];
+		let winner = usize::from(duel.duelists[0] == victim.id);
+		duel.wins[winner] += 1;
+
+		if duel.wins[winner] * 2 > duel.best_of {
+			let finished = duels.swap_remove(index);
+			drop(duels);
+			finish(server, &finished, winner, false).await;
+			return;
+		}
+
+		let standings = format!("round won by {} ({}-{})", duel.names[winner], duel.wins[0], duel.wins[1]);
+		notify_both(server, duel, standings).await;
+		self.start_round(server, duel);
+	}
+
+	pub async fn on_leave(&self, server: &Server, player: &Player) {
+		self.challenges.write().await.retain(|challenge| challenge.challenger != player.id && challenge.challenged != player.id);
+
+		let mut duels = self.duels.write().await;
+		let Some(index) = duels.iter().position(|duel| duel.duelists.contains(&player.id))
+			else { return; };
+
+		let duel = duels.swap_remove(index);
+		drop(duels);
+		finish(server, &duel, usize::from(duel.duelists[0] == player.id), true).await;
+	}
+
+	async fn take_challenge_of(&self, player: &Player) -> Option<Challenge> {
+		let mut challenges = self.challenges.write().await;
+		let index = challenges
+			.iter()
+			.position(|challenge| challenge.challenged == player.id && challenge.issued.elapsed() < CHALLENGE_TIMEOUT)?;
+
+		Some(challenges.swap_remove(index))
+	}
+
+	fn start_round(&self, server: &Server, duel: &mut Duel) {
+		duel.countdown = Some((Instant::now() + COUNTDOWN, COUNTDOWN.as_secs() + 1));
+
+		let duelists = duel.duelists;
+		let arena = duel.arena.then_some(self.arena);
+		let server_static = server.extend_lifetime();
+		tokio::spawn(async move {//teleports take a while to settle
+			for (index, id) in duelists.into_iter().enumerate() {
+				let Some(duelist) = server_static.find_player_by_id(id).await
+					else { continue; };
+
+				let mut addon_data = duelist.addon_data.write().await;
+				if let Some(ref mut engagement) = addon_data.duel {
+					engagement.fighting = false;
+				}
+				drop(addon_data);
+				refresh_hostility(server_static, &duelist).await;
+				heal(&duelist, FULL_HEAL).await;
+				if let Some(arena) = arena {
+					server_static.teleport(&duelist, arena[index]).await;
+				}
+			}
+		});
+	}
+}
+
+async fn finish(server: &Server, duel: &Duel, winner: usize, forfeit: bool) {
+	for id in duel.duelists {
+		if let Some(duelist) = server.find_player_by_id(id).await {
+			duelist.addon_data.write().await.duel = None;
+			refresh_hostility(server, &duelist).await;
+		}
+	}
+
+	let loser = 1 - winner;
+	let result = if forfeit { "by forfeit".to_owned() } else { format!("{}-{}", duel.wins[winner], duel.wins[loser]) };
+	server.announce(format!(
+		"{} won the duel against {} ({result})",
+		duel.names[winner],
+		duel.names[loser]
+	)).await;
+
+	let placements = [winner, loser].map(|index| Placement {
+		name: duel.names[index].clone(),
+		class: duel.classes[index],
+		team: None,
+		score: i32::from(index == winner)
+	});
+	server.addons.ratings.record(DUEL, &placements).await;
+	server.addons.tournaments.on_duel_result(server, &duel.names[winner], &duel.names[loser]).await;
+}
+
+async fn notify_both(server: &Server, duel: &Duel, message: String) {
+	for id in duel.duelists {
+		if let Some(duelist) = server.find_player_by_id(id).await {
+			duelist.notify(message.clone()).await;
+		}
+	}
+}
+
+async fn identity_of(player: &Player) -> (String, CombatClass) {
+	let character = player.character.read().await;
+	(character.name.clone(), character.combat_class())
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use protocol::packet::common::CreatureId;
+use protocol::packet::common::item::Kind::Weapon;
+use protocol::packet::creature_update::equipment::Slot::{LeftWeapon, RightWeapon};
+use protocol::packet::world_update::Kill;
+use protocol::packet::WorldUpdate;
+
+use crate::addon::pvp::rewards;
+use crate::server::creature::Creature;
+use crate::server::player::Player;
+use crate::server::Server;
+
+///attackers who hit the victim within this time before its death get credited with an assist
+const ASSIST_WINDOW: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Default)]
+pub struct PlayerData {
+	pub kills: u32,
+	pub deaths: u32,
+	pub assists: u32,
+	pub damage_dealt: f32,
+	///who recently damaged this player, when, and with what
+	recent_attackers: HashMap<CreatureId, (Instant, String)>,
+	///names of the players this player killed recently, see [`rewards`]
+	pub recent_victims: Vec<(String, Instant)>
+}
+
+pub async fn record_attack(attacker: &Player, target: &Player, damage: f32, cause: String) {
+	if damage <= 0.0 || attacker.id == target.id {
+		return; //heals and self-inflicted damage
+	}
+
+	attacker.addon_data.write().await.kill_data.damage_dealt += damage;
+	target.addon_data.write().await.kill_data.recent_attackers.insert(attacker.id, (Instant::now(), cause));
+}
+
+///what `attacker` is currently fighting with, for the kill feed
+pub fn weapon_of(attacker: &Creature) -> String {
+	[RightWeapon, LeftWeapon]
+		.into_iter()
+		.find_map(|slot| match attacker.equipment[slot].kind {
+			Weapon(weapon) => Some(format!("{weapon:?}")),
+			_ => None
+		})
+		.unwrap_or_else(|| "bare hands".to_owned())
+}
+
+pub async fn on_death(server: &Server, victim: &Player) {
+	let killer = credit_kill(server, victim).await;
+	server.addons.matches.on_death(server, victim, killer.as_deref()).await;
+	server.addons.duels.on_death(server, victim).await;
+}
+
+///updates the statistics of everyone involved and announces the death, returns the killer if there was one
+async fn credit_kill(server: &Server, victim: &Player) -> Option<Arc<Player>> {
+	let mut addon_data = victim.addon_data.write().await;
+	addon_data.kill_data.deaths += 1;
+	let mut attackers = addon_data.kill_data.recent_attackers
+		.drain()
+		.filter(|(_, (timestamp, _))| timestamp.elapsed() < ASSIST_WINDOW)
+		.collect::<Vec<_>>();
+	drop(addon_data);
+
+	let victim_name = victim.character.read().await.name.clone();
+
+	attackers.sort_by_key(|(_, (timestamp, _))| *timestamp);
+	let Some((killer_id, (_, cause))) = attackers.pop()
+		else {
+			server.announce(format!("{victim_name} died")).await;
+			return None;
+		};
+
+	let mut assisters = vec![];
+	for (assister_id, _) in attackers {
+		if let Some(assister) = server.find_player_by_id(assister_id).await {
+			assister.addon_data.write().await.kill_data.assists += 1;
+			assisters.push(assister);
+		}
+	}
+
+	let Some(killer) = server.find_player_by_id(killer_id).await
+		else {
+			server.announce(format!("{victim_name} died")).await; //killer disconnected in the meantime
+			return None;
+		};
+	killer.addon_data.write().await.kill_data.kills += 1;
+
+	let kill = Kill {
+		killer: killer.id,
+		victim: victim.id,
+		unknown: 0,
+		experience: 0
+	};
+	server.broadcast_batched(&WorldUpdate::from(kill), None).await;
+
+	let killer_name = killer.character.read().await.name.clone();
+	server.announce(format!("{killer_name} killed {victim_name} with {cause}")).await;
+
+	rewards::reward_kill(server, &killer, victim, &assisters).await;
+	Some(killer)
+}
@@ -6,9 +6,9 @@ use tap::Pipe;
 
 use crate::server::{Server, player::Player, creature::Creature};
 
-pub fn create(character: &Creature, owner_id: CreatureId) -> CreatureUpdate {
+pub fn create(character: &Creature, id: CreatureId) -> CreatureUpdate {
     CreatureUpdate {
-        id: CreatureId(owner_id.0 + 2500), //todo: claim id from pool
+        id,
         position: Some(character.position),
         rotation: Some(character.rotation),
         appearance: Some(character.appearance.clone()),
@@ -19,7 +19,7 @@ pub fn create(character: &Creature, owner_id: CreatureId) -> CreatureUpdate {
 
 pub fn create_toggle_packet(source: &Player, enabled: bool) -> CreatureUpdate {
     CreatureUpdate {
-        id: CreatureId(source.id.0 + 2500), //todo: claim id from pool
+        id: source.map_head_id,
         affiliation: Some(if enabled { Affiliation::Player } else { Affiliation::Neutral }),
         ..Default::default()
     }
@@ -31,7 +31,7 @@ pub async fn update(server: &Server, source: &Player, packet: &CreatureUpdate, t
 	}
 
 	let map_head_update = CreatureUpdate {
-		id: CreatureId(source.id.0 + 2500),//todo: claim id from pool
+		id: source.map_head_id,
 		position: packet.position,
 		rotation: packet.rotation,
 		affiliation: Some(Affiliation::Player),
@@ -53,7 +53,7 @@ pub async fn update(server: &Server, source: &Player, packet: &CreatureUpdate, t
 
             !is_source && !is_teammate
         })
-        .map(|player| player.send_ignoring(&map_head_update))
+        .map(|player| player.enqueue(&map_head_update))
         .pipe(join_all)
         .await;
 }
\ No newline at end of file
@@ -0,0 +1,531 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::time::{Duration, Instant};
+
+use tap::Tap;
+use tokio::sync::RwLock;
+
+use protocol::nalgebra::Point3;
+use protocol::packet::{ChatMessageFromServer, CreatureUpdate};
+use protocol::packet::common::CreatureId;
+use protocol::utils::constants::CombatClass;
+
+use crate::addon::pvp;
+use crate::addon::pvp::ratings::Placement;
+use crate::server::player::Player;
+use crate::server::Server;
+use crate::server::utils::heal;
+
+use protocol::packet::common::Item;
+
+use self::arena::Arena;
+use self::capture_the_flag::{Bases, Flags};
+use self::mode::Mode;
+
+pub mod mode;
+pub mod capture_the_flag;
+pub mod arena;
+
+const COUNTDOWN: Duration = Duration::from_secs(5);
+///pause between rounds of elimination modes
+const INTERMISSION: Duration = Duration::from_secs(5);
+///how long the results stay up before a new match can be opened
+const RESULTS_DURATION: Duration = Duration::from_secs(15);
+///teams for team based modes, players otherwise
+const MIN_SIDES: usize = 2;
+const FULL_HEAL: f32 = 9999.0;
+
+///who a score belongs to
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Side {
+	Solo(CreatureId),
+	Team(i32)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Phase {
+	Lobby,
+	Countdown { ends: Instant, last_announced: u64 },
+	Running,
+	Intermission { ends: Instant },
+	Results { ends: Instant }
+}
+
+///remembered on join, so participants can still be rated after they left
+#[derive(Debug)]
+struct Entrant {
+	name: String,
+	class: CombatClass,
+	side: Side,
+	left: bool
+}
+
+#[derive(Debug)]
+struct Match {
+	mode: Mode,
+	phase: Phase,
+	participants: Vec<CreatureId>,
+	entrants: HashMap<CreatureId, Entrant>,
+	scores: HashMap<Side, i32>,
+	///participants who died during the current round of an elimination mode
+	eliminated: HashSet<CreatureId>,
+	started: Option<Instant>,
+	next_spawn_point: usize,
+	///only used by [`Mode::CaptureTheFlag`]
+	flags: Flags
+}
+
+///runs one organized match at a time: lobby -> countdown -> running -> results
+#[derive(Debug)]
+pub struct MatchManager {
+	current: RwLock<Option<Match>>,
+	spawn_points: Vec<Point3<i64>>,
+	flag_bases: Bases,
+	arena: Arena
+}
+
+impl Default for MatchManager {
+	fn default() -> Self {
+		let file_content = match fs::read_to_string(Self::FILE_PATH) {
+			Ok(content) => content,
+
+			Err(error) if error.kind() == NotFound => {
+				concat!(0x8020800000,';',0x8020800000)
+					.tap(|content| fs::write(Self::FILE_PATH, content).unwrap())
+					.to_owned()
+			}
+
+			Err(error) => panic!("failed to load {} - {}", Self::FILE_PATH, error)
+		};
+
+		Self {
+			current: RwLock::default(),
+			spawn_points: file_content.lines().map(|line| {
+				let splits: [&str; 2] = line
+					.split(';')
+					.collect::<Vec<_>>()
+					.try_into()
+					.unwrap();
+
+				Point3::new(
+					splits[0].parse().unwrap(),
+					splits[1].parse().unwrap(),
+					0_i64
+				)
+			}).collect(),
+			flag_bases: Bases::default(),
+			arena: Arena::default()
+		}
+	}
+}
+
+impl MatchManager {
+	const FILE_PATH: &'static str = "spawn_points.csv";
+
+	pub async fn open(&self, server: &Server, mode: Mode) -> Result<(), &'static str> {
+		if self.spawn_points.is_empty() {
+			return Err("no spawn points are configured");
+		}
+
+		let mut current = self.current.write().await;
+		if current.as_ref().is_some_and(|game| !matches!(game.phase, Phase::Results { .. })) {
+			return Err("a match is already in progress");
+		}
+
+		*current = Some(Match {
+			mode,
+			phase: Phase::Lobby,
+			participants: vec![],
+			entrants: HashMap::new(),
+			scores: HashMap::new(),
+			eliminated: HashSet::new(),
+			started: None,
+			next_spawn_point: 0,
+			flags: Flags::default()
+		});
+		drop(current);
+
+		server.announce(format!("a {mode} match is about to start, use /join to participate")).await;
+		Ok(())
+	}
+
+	#[expect(clippy::significant_drop_tightening, reason = "cannot drop any earlier")]
+	pub async fn join(&self, player: &Player) -> Result<(), &'static str> {
+		let mut current = self.current.write().await;
+		let game = current.as_mut().ok_or("no match is open")?;
+
+		if !matches!(game.phase, Phase::Lobby) {
+			return Err("the match has started already");
+		}
+		if game.mode.is_team_based() && player.addon_data.read().await.team.is_none() {
+			return Err("this mode is team based, join a team first (/team)");
+		}
+		if !game.participants.contains(&player.id) {
+			game.participants.push(player.id);
+
+			let character = player.character.read().await;
+			let (name, class) = (character.name.clone(), character.combat_class());
+			drop(character);
+			game.entrants.insert(player.id, Entrant {
+				name,
+				class,
+				side: side_of(game.mode, player).await,
+				left: false
+			});
+		}
+		Ok(())
+	}
+
+	#[expect(clippy::significant_drop_tightening, reason = "cannot drop any earlier")]
+	pub async fn begin(&self, server: &Server) -> Result<(), &'static str> {
+		let mut current = self.current.write().await;
+		let game = current.as_mut().ok_or("no match is open")?;
+
+		if !matches!(game.phase, Phase::Lobby) {
+			return Err("the match has started already");
+		}
+
+		let mut sides = HashSet::new();
+		for participant in &game.participants {
+			if let Some(player) = server.find_player_by_id(*participant).await {
+				sides.insert(side_of(game.mode, &player).await);
+			}
+		}
+		if sides.len() < MIN_SIDES {
+			return Err("not enough participants, at least two sides are needed");
+		}
+
+		game.phase = Phase::Countdown {
+			ends: Instant::now() + COUNTDOWN,
+			last_announced: COUNTDOWN.as_secs() + 1
+		};
+		Ok(())
+	}
+
+	pub async fn cancel(&self, server: &Server) -> Result<(), &'static str> {
+		let mut game = self.current.write().await.take().ok_or("no match is open")?;
+		game.flags.clear(server).await;
+		set_eliminated_later(server, game.eliminated.drain().collect(), false);
+		server.announce("the match has been cancelled").await;
+		Ok(())
+	}
+
+	#[expect(clippy::significant_drop_tightening, reason = "cannot drop any earlier")]
+	pub async fn status(&self, server: &Server) -> String {
+		let current = self.current.read().await;
+		let Some(game) = current.as_ref()
+			else { return "no match is open".to_owned(); };
+
+		let phase = match game.phase {
+			Phase::Lobby              => "lobby",
+			Phase::Countdown { .. }   => "countdown",
+			Phase::Running            => "running",
+			Phase::Intermission { .. } => "intermission",
+			Phase::Results { .. }     => "over"
+		};
+		format!("{} ({phase}), {} participants - {}", game.mode, game.participants.len(), standings_of(server, game).await)
+	}
+
+	pub async fn on_tick(&self, server: &Server) {
+		let mut current = self.current.write().await;
+		let Some(game) = current.as_mut()
+			else { return; };
+
+		let now = Instant::now();
+		match game.phase {
+			Phase::Countdown { ends, last_announced } => {
+				if now >= ends {
+					game.started = Some(now);
+					self.start_round(server, game).await;
+					announce_later(server, "go!".to_owned());
+					return;
+				}
+
+				let remaining = (ends - now).as_secs() + 1;
+				if remaining < last_announced {
+					game.phase = Phase::Countdown { ends, last_announced: remaining };
+					chat_later(server, remaining.to_string());
+				}
+			}
+			Phase::Running => {
+				for team in game.flags.on_tick(server).await {
+					let score = game.scores.entry(Side::Team(team)).or_default();
+					*score += 1;
+					if *score >= game.mode.score_limit() {
+						finish(server, game, "score limit reached").await;
+						return;
+					}
+				}
+				if game.started.is_some_and(|started| started.elapsed() >= game.mode.time_limit()) {
+					finish(server, game, "time is up").await;
+				}
+			}
+			Phase::Intermission { ends } => {
+				if now >= ends {
+					self.start_round(server, game).await;
+				}
+			}
+			Phase::Results { ends } => {
+				if now >= ends {
+					*current = None;
+				}
+			}
+			Phase::Lobby => ()
+		}
+	}
+
+	#[expect(clippy::significant_drop_tightening, reason = "cannot drop any earlier")]
+	pub async fn on_death(&self, server: &Server, victim: &Player, killer: Option<&Player>) {
+		let mut current = self.current.write().await;
+		let Some(game) = current.as_mut()
+			else { return; };
+
+		if !matches!(game.phase, Phase::Running) || !game.participants.contains(&victim.id) {
+			return;
+		}
+
+		if game.mode == Mode::CaptureTheFlag {
+			game.flags.release(server, victim).await;
+			return; //only captures count
+		}
+
+		if game.mode.eliminates() {
+			game.eliminated.insert(victim.id);
+			set_eliminated_later(server, vec![victim.id], true);
+			check_round_over(server, game).await;
+			return;
+		}
+
+		let Some(killer) = killer.filter(|killer| killer.id != victim.id && game.participants.contains(&killer.id))
+			else { return; };
+
+		let side = side_of(game.mode, killer).await;
+		let score = game.scores.entry(side).or_default();
+		*score += 1;
+		if *score >= game.mode.score_limit() {
+			finish(server, game, "score limit reached").await;
+		}
+	}
+
+	///keeps participants of a running match inside the arena
+	pub async fn on_creature_update(&self, server: &Server, source: &Player, packet: &CreatureUpdate) {
+		let Some(position) = packet.position
+			else { return; };
+
+		let enforced = self.current
+			.read().await
+			.as_ref()
+			.is_some_and(|game| matches!(game.phase, Phase::Running) && game.participants.contains(&source.id));
+
+		if enforced {
+			self.arena.enforce(server, source, position).await;
+		}
+	}
+
+	#[expect(clippy::significant_drop_tightening, reason = "cannot drop any earlier")]
+	pub async fn on_respawn(&self, server: &Server, player: &Player) {
+		let mut current = self.current.write().await;
+		let Some(game) = current.as_mut()
+			else { return; };
+
+		if matches!(game.phase, Phase::Running) && !game.mode.eliminates() && game.participants.contains(&player.id) {
+			let spawn_point = self.next_spawn_point(game);
+			teleport_later(server, vec![(player.id, spawn_point)]);
+		}
+	}
+
+	#[expect(clippy::significant_drop_tightening, reason = "cannot drop any earlier")]
+	pub async fn on_leave(&self, server: &Server, player: &Player) {
+		let mut current = self.current.write().await;
+		let Some(game) = current.as_mut()
+			else { return; };
+
+		game.participants.retain(|participant| *participant != player.id);
+		match game.entrants.get_mut(&player.id) {
+			Some(entrant) if !matches!(game.phase, Phase::Lobby) => entrant.left = true, //rated as last
+			_ => { game.entrants.remove(&player.id); }
+		}
+		if game.eliminated.remove(&player.id) {
+			set_eliminated_later(server, vec![player.id], false);
+		}
+		game.flags.release(server, player).await;
+		if matches!(game.phase, Phase::Running) && game.mode.eliminates() {
+			check_round_over(server, game).await;
+		}
+	}
+
+	///called after a flag got picked up, returns whether it was handled (the flag must not end up in an inventory)
+	#[expect(clippy::significant_drop_tightening, reason = "cannot drop any earlier")]
+	pub async fn on_pick_up(&self, server: &Server, player: &Player, item: &Item) -> bool {
+		let mut current = self.current.write().await;
+		let Some(game) = current.as_mut()
+			else { return false; };
+
+		if !game.flags.contains(item) {
+			return false;
+		}
+
+		if matches!(game.phase, Phase::Running) && game.participants.contains(&player.id) {
+			game.flags.pick_up(server, player, item).await;
+		} else {
+			game.flags.put_back(server, item).await;
+		}
+		true
+	}
+
+	async fn start_round(&self, server: &Server, game: &mut Match) {
+		game.phase = Phase::Running;
+		set_eliminated_later(server, game.eliminated.drain().collect(), false);
+		if game.mode == Mode::CaptureTheFlag {
+			game.flags.clear(server).await;
+			game.flags = Flags::place(server, &self.flag_bases).await;
+		}
+
+		let destinations = game.participants
+			.clone()
+			.into_iter()
+			.map(|participant| (participant, self.next_spawn_point(game)))
+			.collect();
+		teleport_later(server, destinations);
+	}
+
+	fn next_spawn_point(&self, game: &mut Match) -> Point3<i64> {
+		let spawn_point = self.spawn_points[game.next_spawn_point % self.spawn_points.len()];
+		game.next_spawn_point += 1;
+		spawn_point
+	}
+}
+
+async fn side_of(mode: Mode, player: &Player) -> Side {
+	if mode.is_team_based() && let Some(team) = player.addon_data.read().await.team {
+		return Side::Team(team);
+	}
+	Side::Solo(player.id)
+}
+
+///ends the current round of an elimination mode once only one side is left standing
+async fn check_round_over(server: &Server, game: &mut Match) {
+	let mut sides_alive = HashSet::new();
+	for participant in &game.participants {
+		if game.eliminated.contains(participant) {
+			continue;
+		}
+		if let Some(player) = server.find_player_by_id(*participant).await {
+			sides_alive.insert(side_of(game.mode, &player).await);
+		}
+	}
+
+	if sides_alive.len() > 1 {
+		return;
+	}
+
+	let Some(winner) = sides_alive.into_iter().next()
+		else {
+			game.phase = Phase::Intermission { ends: Instant::now() + INTERMISSION };
+			announce_later(server, "nobody survived this round".to_owned());
+			return;
+		};
+
+	let score = game.scores.entry(winner).or_default();
+	*score += 1;
+	if *score >= game.mode.score_limit() {
+		finish(server, game, "round limit reached").await;
+		return;
+	}
+
+	game.phase = Phase::Intermission { ends: Instant::now() + INTERMISSION };
+	announce_later(server, format!("{} won the round", name_of(server, winner).await));
+}
+
+async fn finish(server: &Server, game: &mut Match, reason: &str) {
+	game.flags.clear(server).await;
+	set_eliminated_later(server, game.eliminated.drain().collect(), false);
+	game.phase = Phase::Results { ends: Instant::now() + RESULTS_DURATION };
+	announce_later(server, format!("{} over ({reason}): {}", game.mode, standings_of(server, game).await));
+
+	let placements = game.entrants
+		.values()
+		.map(|entrant| Placement {
+			name: entrant.name.clone(),
+			class: entrant.class,
+			team: match entrant.side {
+				Side::Team(team) => Some(team),
+				Side::Solo(_) => None
+			},
+			score:
+				if entrant.left { i32::MIN }
+				else            { game.scores.get(&entrant.side).copied().unwrap_or_default() }
+		})
+		.collect::<Vec<_>>();
+	server.addons.ratings.record(game.mode.name(), &placements).await;
+}
+
+async fn standings_of(server: &Server, game: &Match) -> String {
+	let mut scores = game.scores.iter().collect::<Vec<_>>();
+	scores.sort_unstable_by_key(|(_, score)| -**score);
+
+	let mut standings = vec![];
+	for (rank, (side, score)) in scores.into_iter().enumerate() {
+		standings.push(format!("{}. {} ({score})", rank + 1, name_of(server, *side).await));
+	}
+
+	if standings.is_empty() {
+		return "no scores yet".to_owned();
+	}
+	standings.join(", ")
+}
+
+async fn name_of(server: &Server, side: Side) -> String {
+	match side {
+		Side::Team(team) => format!("team {team}"),
+		Side::Solo(id) => match server.find_player_by_id(id).await {
+			Some(player) => player.character.read().await.name.clone(),
+			None => "(disconnected)".to_owned()
+		}
+	}
+}
+
+///announcements are slow (they get posted to discord), so they mustn't block the tick
+fn announce_later(server: &Server, text: String) {
+	let server_static = server.extend_lifetime();
+	tokio::spawn(async move {
+		server_static.announce(text).await;
+	});
+}
+
+///for messages too frequent for discord, like the countdown
+fn chat_later(server: &Server, text: String) {
+	let server_static = server.extend_lifetime();
+	tokio::spawn(async move {
+		server_static.broadcast(&ChatMessageFromServer { source: CreatureId(0), text }, None).await;
+	});
+}
+
+///teleports take a while to settle, so they mustn't block the tick either
+fn teleport_later(server: &Server, destinations: Vec<(CreatureId, Point3<i64>)>) {
+	let server_static = server.extend_lifetime();
+	tokio::spawn(async move {
+		for (id, destination) in destinations {
+			let Some(player) = server_static.find_player_by_id(id).await
+				else { continue; };
+
+			heal(&player, FULL_HEAL).await;
+			server_static.teleport(&player, destination).await;
+		}
+	});
+}
+
+///takes players out of the fight or back into it, see [`pvp::is_hostile`]
+fn set_eliminated_later(server: &Server, players: Vec<CreatureId>, eliminated: bool) {
+	let server_static = server.extend_lifetime();
+	tokio::spawn(async move {
+		for id in players {
+			let Some(player) = server_static.find_player_by_id(id).await
+				else { continue; };
+
+			player.addon_data.write().await.eliminated = eliminated;
+			pvp::refresh_hostility(server_static, &player).await;
+		}
+	});
+}
@@ -0,0 +1,195 @@
+use std::f64::consts::TAU;
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use tap::Tap;
+
+use protocol::nalgebra::{Point2, Point3, Vector2, Vector3};
+use protocol::packet::{Hit, WorldUpdate};
+use protocol::packet::hit::Kind::Normal;
+use protocol::packet::world_update::{particle, Particle};
+use protocol::utils::constants::SIZE_BLOCK;
+
+use crate::server::player::Player;
+use crate::server::Server;
+
+use self::Policy::*;
+
+///what happens to participants who leave the arena during a match
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Policy {
+	TeleportBack,
+	DamageOverTime
+}
+
+impl FromStr for Policy {
+	type Err = String;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		match name {
+			"teleport_back"    => Ok(TeleportBack),
+			"damage_over_time" => Ok(DamageOverTime),
+			_ => Err(format!("unknown policy {name}, expected teleport_back or damage_over_time"))
+		}
+	}
+}
+
+///participants closer than this to the boundary get to see it
+const WARNING_DISTANCE: i64 = SIZE_BLOCK * 8;
+///particles fade quickly, so the wall needs to be resent while someone is near
+const WALL_INTERVAL: Duration = Duration::from_millis(500);
+const MESSAGE_INTERVAL: Duration = Duration::from_secs(5);
+const ENFORCEMENT_INTERVAL: Duration = Duration::from_secs(1);
+///how far inside the boundary players get put back
+const RETURN_DEPTH: i64 = SIZE_BLOCK * 4;
+const DAMAGE_PER_SECOND: f32 = 200.0;
+///how much of the wall is shown, left and right of the player
+const WALL_WIDTH: i64 = SIZE_BLOCK * 8;
+const WALL_HEIGHT: i64 = SIZE_BLOCK * 4;
+const WALL_SPACING: i64 = SIZE_BLOCK;
+
+///when each of these was last applied to a player
+#[derive(Debug, Default)]
+pub struct PlayerData {
+	wall: Option<Instant>,
+	message: Option<Instant>,
+	enforcement: Option<Instant>
+}
+
+///the circle match participants have to stay in
+#[derive(Debug)]
+pub struct Arena {
+	center: Point2<i64>,
+	radius: i64,
+	policy: Policy
+}
+
+impl Default for Arena {
+	fn default() -> Self {
+		let file_content = match fs::read_to_string(Self::FILE_PATH) {
+			Ok(content) => content,
+
+			Err(error) if error.kind() == NotFound => {
+				concat!(0x8020800000,';',0x8020800000,';',64,';',"teleport_back")
+					.tap(|content| fs::write(Self::FILE_PATH, content).unwrap())
+					.to_owned()
+			}
+
+			Err(error) => panic!("failed to load {} - {}", Self::FILE_PATH, error)
+		};
+
+		let [x, y, radius, policy]: [&str; 4] = file_content
+			.trim()
+			.split(';')
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap_or_else(|_| panic!("{} must be x;y;radius;policy", Self::FILE_PATH));
+		let [x, y, radius] = [x, y, radius].map(|split| split.parse::<i64>().unwrap());
+
+		Self {
+			center: Point2::new(x, y),
+			radius: radius * SIZE_BLOCK,
+			policy: policy.parse().unwrap()
+		}
+	}
+}
+
+impl Arena {
+	const FILE_PATH: &'static str = "arena.csv";
+
+	pub async fn enforce(&self, server: &Server, player: &Player, position: Point3<i64>) {
+		let offset = (position.xy() - self.center).cast::<f64>();
+		let distance = offset.norm();
+		if distance < (self.radius - WARNING_DISTANCE) as f64 {
+			return;
+		}
+
+		let direction = offset.try_normalize(f64::EPSILON).unwrap_or_else(Vector2::x);
+		let now = Instant::now();
+		let mut addon_data = player.addon_data.write().await;
+		let arena_data = &mut addon_data.arena_data;
+
+		if distance <= self.radius as f64 {
+			let show_wall = arena_data.wall.is_none_or(|last_wall| last_wall.elapsed() >= WALL_INTERVAL);
+			let show_message = arena_data.message.is_none_or(|last_message| last_message.elapsed() >= MESSAGE_INTERVAL);
+			if show_wall {
+				arena_data.wall = Some(now);
+			}
+			if show_message {
+				arena_data.message = Some(now);
+			}
+			drop(addon_data);
+
+			if show_wall {
+				player.enqueue(&WorldUpdate::from(self.wall_near(direction, position.z))).await;
+			}
+			if show_message {
+				player.notify("you are approaching the arena boundary").await;
+			}
+			return;
+		}
+
+		if arena_data.enforcement.is_some_and(|last_enforcement| last_enforcement.elapsed() < ENFORCEMENT_INTERVAL) {
+			return;
+		}
+		arena_data.enforcement = Some(now);
+		drop(addon_data);
+
+		player.notify("get back into the arena!").await;
+		match self.policy {
+			TeleportBack => {
+				let destination = self.point_at(direction, self.radius - RETURN_DEPTH);
+				let server_static = server.extend_lifetime();
+				let player_id = player.id;
+				tokio::spawn(async move {//teleports take a while to settle
+					if let Some(target) = server_static.find_player_by_id(player_id).await {
+						server_static.teleport(&target, Point3::new(destination.x, destination.y, position.z)).await;
+					}
+				});
+			}
+			DamageOverTime => {
+				let hit = Hit {
+					attacker: player.id,
+					target: player.id,
+					damage: DAMAGE_PER_SECOND * ENFORCEMENT_INTERVAL.as_secs_f32(),
+					position,
+					kind: Normal,
+					..Default::default()
+				};
+				player.enqueue(&WorldUpdate::from(hit)).await;
+			}
+		}
+	}
+
+	fn point_at(&self, direction: Vector2<f64>, distance: i64) -> Point2<i64> {
+		self.center + (direction * distance as f64).map(|scalar| scalar as i64)
+	}
+
+	///a patch of the boundary right in front of the player
+	fn wall_near(&self, direction: Vector2<f64>, height: i64) -> Vec<Particle> {
+		let angle = direction.y.atan2(direction.x);
+		let angular_spacing = WALL_SPACING as f64 / self.radius as f64;
+		let steps = WALL_WIDTH / WALL_SPACING;
+
+		let mut particles = vec![];
+		for step in -steps..=steps {
+			let step_angle = (step as f64).mul_add(angular_spacing, angle) % TAU;
+			let point = self.point_at(Vector2::new(step_angle.cos(), step_angle.sin()), self.radius);
+
+			for level in (0..=WALL_HEIGHT).step_by(WALL_SPACING as usize) {
+				particles.push(Particle {
+					position: Point3::new(point.x, point.y, height + level),
+					velocity: Vector3::zeros(),
+					color: [1.0, 0.2, 0.2, 1.0].into(),
+					size: 0.5,
+					count: 1,
+					kind: particle::Kind::NoGravity,
+					spread: 0.0
+				});
+			}
+		}
+		particles
+	}
+}
@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::time::{Duration, Instant};
+
+use tap::Tap;
+
+use protocol::nalgebra::{Point2, Point3};
+use protocol::packet::{StatusEffect, WorldUpdate};
+use protocol::packet::common::{CreatureId, Item, Race};
+use protocol::packet::common::item::Kind::Quest;
+use protocol::packet::common::item::kind::Quest::AmuletYellow;
+use protocol::packet::status_effect::Kind::Anger;
+use protocol::packet::world_update::Mission;
+use protocol::packet::world_update::mission::{Objective, State as MissionState};
+use protocol::utils::constants::{SIZE_BLOCK, SIZE_SECTOR};
+
+use crate::server::player::Player;
+use crate::server::Server;
+use crate::server::spatial_index::zone_of;
+
+use super::announce_later;
+
+///how close a carrier needs to get to their own base to score
+const CAPTURE_RADIUS: i64 = SIZE_BLOCK * 4;
+///how long a dropped flag stays on the ground before it returns to its base
+const RETURN_TIMEOUT: Duration = Duration::from_secs(30);
+///distinguishes flags from regular drops. flags never end up in an inventory, so nobody can drop a counterfeit
+const FLAG_SEED: i32 = 0xF1A6;
+
+///where each team keeps its flag
+#[derive(Debug)]
+pub struct Bases {
+	positions: HashMap<i32, Point3<i64>>
+}
+
+impl Default for Bases {
+	fn default() -> Self {
+		let file_content = match fs::read_to_string(Self::FILE_PATH) {
+			Ok(content) => content,
+
+			Err(error) if error.kind() == NotFound => {
+				concat!(1,';',0x801F800000,';',0x8020800000,';',0,'\n',2,';',0x8021800000,';',0x8020800000,';',0)
+					.tap(|content| fs::write(Self::FILE_PATH, content).unwrap())
+					.to_owned()
+			}
+
+			Err(error) => panic!("failed to load {} - {}", Self::FILE_PATH, error)
+		};
+
+		Self {
+			positions: file_content.lines().map(|line| {
+				let splits: [&str; 4] = line
+					.split(';')
+					.collect::<Vec<_>>()
+					.try_into()
+					.unwrap();
+
+				(
+					splits[0].parse().unwrap(),
+					Point3::new(
+						splits[1].parse().unwrap(),
+						splits[2].parse().unwrap(),
+						splits[3].parse().unwrap()
+					)
+				)
+			}).collect()
+		}
+	}
+}
+
+impl Bases {
+	const FILE_PATH: &'static str = "flag_bases.csv";
+}
+
+#[derive(Debug)]
+struct Flag {
+	team: i32,
+	base: Point3<i64>,
+	state: State,
+	///where the map marker was last placed, to avoid resending it every tick
+	marker_zone: Point2<i32>
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+	AtBase,
+	Carried(CreatureId),
+	Dropped { position: Point3<i64>, since: Instant }
+}
+
+///all flags of a running ctf match
+#[derive(Debug, Default)]
+pub struct Flags(Vec<Flag>);
+
+impl Flags {
+	pub async fn place(server: &Server, bases: &Bases) -> Self {
+		let mut flags = vec![];
+		for (team, base) in &bases.positions {
+			server.add_drop(flag_item(*team), *base, 0.0).await;
+			server.broadcast_batched(&WorldUpdate::from(marker(*team, *base)), None).await;
+
+			flags.push(Flag {
+				team: *team,
+				base: *base,
+				state: State::AtBase,
+				marker_zone: zone_of(*base)
+			});
+		}
+		Self(flags)
+	}
+
+	pub fn contains(&self, item: &Item) -> bool {
+		self.0.iter().any(|flag| *item == flag_item(flag.team))
+	}
+
+	///called after `item` has been removed from the ground by `player`
+	pub async fn pick_up(&mut self, server: &Server, player: &Player, item: &Item) {
+		let Some(flag) = self.0.iter_mut().find(|flag| *item == flag_item(flag.team))
+			else { return; };
+
+		let own_team = player.addon_data.read().await.team;
+		let name = player.character.read().await.name.clone();
+
+		if own_team != Some(flag.team) {
+			flag.state = State::Carried(player.id);
+			set_carrier_effect(server, player.id, true).await;
+			announce_later(server, format!("{name} took the flag of team {}", flag.team));
+			return;
+		}
+
+		if matches!(flag.state, State::Dropped { .. }) {
+			announce_later(server, format!("{name} returned the flag of team {}", flag.team));
+		}
+		flag.state = State::AtBase;
+		place_at(server, flag, flag.base).await;
+	}
+
+	///puts a flag back where it was, for when someone who isn't playing picked it up
+	pub async fn put_back(&self, server: &Server, item: &Item) {
+		let Some(flag) = self.0.iter().find(|flag| *item == flag_item(flag.team))
+			else { return; };
+
+		let position = match flag.state {
+			State::Dropped { position, .. } => position,
+			_ => flag.base
+		};
+		server.add_drop(item.clone(), position, 0.0).await;
+	}
+
+	///drops any flag carried by `player` where they are, on death or when they leave
+	pub async fn release(&mut self, server: &Server, player: &Player) {
+		for flag in &mut self.0 {
+			if !matches!(flag.state, State::Carried(carrier) if carrier == player.id) {
+				continue;
+			}
+
+			let position = player.character.read().await.position;
+			flag.state = State::Dropped { position, since: Instant::now() };
+			set_carrier_effect(server, player.id, false).await;
+			place_at(server, flag, position).await;
+			announce_later(server, format!("the flag of team {} was dropped", flag.team));
+		}
+	}
+
+	///returns the teams that scored a capture
+	pub async fn on_tick(&mut self, server: &Server) -> Vec<i32> {
+		let mut captures = vec![];
+		let homes = self.0
+			.iter()
+			.map(|flag| (flag.team, flag.base))
+			.collect::<HashMap<_, _>>();
+
+		for flag in &mut self.0 {
+			match flag.state {
+				State::AtBase => (),
+				State::Carried(carrier_id) => {
+					let Some(carrier) = server.find_player_by_id(carrier_id).await
+						else { continue; };
+
+					let position = carrier.character.read().await.position;
+					if zone_of(position) != flag.marker_zone {
+						flag.marker_zone = zone_of(position);
+						server.broadcast_batched(&WorldUpdate::from(marker(flag.team, position)), None).await;
+					}
+
+					let Some(team) = carrier.addon_data.read().await.team
+						else { continue; };
+					let Some(home) = homes.get(&team)
+						else { continue; };
+
+					if (position - home).xy().abs().max() <= CAPTURE_RADIUS {
+						flag.state = State::AtBase;
+						set_carrier_effect(server, carrier_id, false).await;
+						place_at(server, flag, flag.base).await;
+						announce_later(server, format!("{} captured the flag of team {}", carrier.character.read().await.name, flag.team));
+						captures.push(team);
+					}
+				}
+				State::Dropped { position, since } => {
+					if since.elapsed() < RETURN_TIMEOUT {
+						continue;
+					}
+
+					remove_from_ground(server, flag.team, position).await;
+					flag.state = State::AtBase;
+					place_at(server, flag, flag.base).await;
+					announce_later(server, format!("the flag of team {} returned to its base", flag.team));
+				}
+			}
+		}
+		captures
+	}
+
+	///removes every trace of the flags from the world
+	pub async fn clear(&mut self, server: &Server) {
+		for flag in self.0.drain(..) {
+			match flag.state {
+				State::AtBase => remove_from_ground(server, flag.team, flag.base).await,
+				State::Dropped { position, .. } => remove_from_ground(server, flag.team, position).await,
+				State::Carried(carrier) => set_carrier_effect(server, carrier, false).await
+			}
+
+			let removal = Mission {
+				objective: Objective::RemoveMission,
+				..marker(flag.team, flag.base)
+			};
+			server.broadcast_batched(&WorldUpdate::from(removal), None).await;
+		}
+	}
+}
+
+fn flag_item(team: i32) -> Item {
+	Item {
+		kind: Quest(AmuletYellow),
+		seed: FLAG_SEED + team,
+		..Default::default()
+	}
+}
+
+async fn place_at(server: &Server, flag: &mut Flag, position: Point3<i64>) {
+	flag.marker_zone = zone_of(position);
+	server.add_drop(flag_item(flag.team), position, 0.0).await;
+	server.broadcast_batched(&WorldUpdate::from(marker(flag.team, position)), None).await;
+}
+
+async fn remove_from_ground(server: &Server, team: i32, position: Point3<i64>) {
+	let zone = zone_of(position);
+	if let Some(index) = server.find_drop(zone, &flag_item(team)).await {
+		server.remove_drop(zone, index).await;
+	}
+}
+
+///crossed swords on the map showing where a flag currently is
+fn marker(team: i32, position: Point3<i64>) -> Mission {
+	Mission {
+		sector: position.xy().map(|scalar| (scalar / SIZE_SECTOR) as i32),
+		unknown_a: 0,
+		unknown_b: 0,
+		unknown_c: 0,
+		id: team,
+		objective: Objective::Monster, //places the marker at `zone`, which is more precise than `sector`
+		race: Race::default(),
+		level: 1,
+		rarity: 0,
+		state: MissionState::Ready,
+		progress_current: 0,
+		progress_maximum: 0,
+		zone: zone_of(position)
+	}
+}
+
+///makes flag carriers glow so everyone can tell who to chase
+async fn set_carrier_effect(server: &Server, carrier: CreatureId, enabled: bool) {
+	let effect = StatusEffect {
+		source: carrier,
+		target: carrier,
+		kind: Anger,
+		modifier: 0.0,
+		duration: if enabled { i32::MAX } else { 0 },
+		creature_id3: carrier
+	};
+
+	if let Some(player) = server.find_player_by_id(carrier).await {
+		player.addon_data.write().await.anti_cheat_data.expect_echo(&effect); //otherwise the echo looks like a forged status effect
+	}
+	server.broadcast_batched(&WorldUpdate::from(effect), None).await;
+}
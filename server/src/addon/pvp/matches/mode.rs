@@ -0,0 +1,78 @@
+use std::fmt::{Display, Formatter};
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Mode {
+	///every player for themselves, most kills wins
+	Deathmatch,
+	///teams (see `/team`) compete for the most kills
+	TeamDeathmatch,
+	///rounds of elimination without respawns, the last team alive wins the round
+	LastTeamStanding,
+	///teams steal the flag from the enemy base and bring it home
+	CaptureTheFlag
+}
+
+impl Mode {
+	pub const ALL: [Self; 4] = [
+		Self::Deathmatch,
+		Self::TeamDeathmatch,
+		Self::LastTeamStanding,
+		Self::CaptureTheFlag
+	];
+
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Deathmatch       => "dm",
+			Self::TeamDeathmatch   => "tdm",
+			Self::LastTeamStanding => "lts",
+			Self::CaptureTheFlag   => "ctf"
+		}
+	}
+
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::ALL
+			.into_iter()
+			.find(|mode| mode.name() == name)
+	}
+
+	///kills for deathmatches, won rounds for elimination, captures for ctf
+	pub const fn score_limit(self) -> i32 {
+		match self {
+			Self::Deathmatch       => 20,
+			Self::TeamDeathmatch   => 50,
+			Self::LastTeamStanding |
+			Self::CaptureTheFlag   => 3
+		}
+	}
+
+	pub const fn time_limit(self) -> Duration {
+		match self {
+			Self::Deathmatch       => Duration::from_secs(10 * 60),
+			Self::TeamDeathmatch   => Duration::from_secs(15 * 60),
+			Self::LastTeamStanding |
+			Self::CaptureTheFlag   => Duration::from_secs(20 * 60)
+		}
+	}
+
+	pub const fn is_team_based(self) -> bool {
+		matches!(self, Self::TeamDeathmatch | Self::LastTeamStanding | Self::CaptureTheFlag)
+	}
+
+	///whether dying takes a player out for the rest of the round
+	pub const fn eliminates(self) -> bool {
+		matches!(self, Self::LastTeamStanding)
+	}
+}
+
+impl Display for Mode {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Deathmatch       => "deathmatch",
+			Self::TeamDeathmatch   => "team deathmatch",
+			Self::LastTeamStanding => "last team standing",
+			Self::CaptureTheFlag   => "capture the flag"
+		})
+	}
+}
@@ -0,0 +1,278 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::iter;
+use std::time::Duration;
+
+use colour::red_ln;
+use tokio::fs as tokio_fs;
+use tokio::sync::RwLock;
+use tokio::time::sleep;
+
+use protocol::utils::constants::CombatClass;
+use protocol::utils::constants::combat_classes::*;
+
+use crate::addon::pvp::matches::mode::Mode;
+use crate::server::Server;
+use crate::server::utils::{escape_field, split_fields};
+
+const INITIAL_RATING: f32 = 1500.0;
+const K_FACTOR: f32 = 32.0;
+///ratings move faster during the first few games, so newcomers quickly end up where they belong
+const PROVISIONAL_GAMES: u32 = 10;
+const PROVISIONAL_K_FACTOR: f32 = 64.0;
+const LEADERBOARD_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+pub const LEADERBOARD_SIZE: usize = 10;
+
+///ratings of duels are kept next to those of the match modes
+pub const DUEL: &str = "duel";
+
+const CLASSES: [(CombatClass, &str); 8] = [
+	(BERSERKER , "berserker"),
+	(GUARDIAN  , "guardian"),
+	(SNIPER    , "sniper"),
+	(SCOUT     , "scout"),
+	(FIRE_MAGE , "firemage"),
+	(WATER_MAGE, "watermage"),
+	(ASSASSIN  , "assassin"),
+	(NINJA     , "ninja")
+];
+
+///names are claimed while their player is online and checked against their fingerprint on join,
+///so they identify a player well enough, unlike addresses which change
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+struct Key {
+	player: String,
+	mode: String,
+	class: &'static str
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rating {
+	pub value: f32,
+	pub games: u32
+}
+
+impl Default for Rating {
+	fn default() -> Self {
+		Self {
+			value: INITIAL_RATING,
+			games: 0
+		}
+	}
+}
+
+///how a player did in a duel or match
+#[derive(Debug)]
+pub struct Placement {
+	pub name: String,
+	pub class: CombatClass,
+	///teammates aren't rated against each other
+	pub team: Option<i32>,
+	///higher beats lower
+	pub score: i32
+}
+
+///elo ratings, separate for each mode and class
+#[derive(Debug)]
+pub struct Ratings {
+	table: RwLock<HashMap<Key, Rating>>
+}
+
+impl Default for Ratings {
+	fn default() -> Self {
+		let file_content = match fs::read_to_string(Self::FILE_PATH) {
+			Ok(content) => content,
+			Err(error) if error.kind() == NotFound => String::new(),
+			Err(error) => panic!("failed to load {} - {}", Self::FILE_PATH, error)
+		};
+
+		Self {
+			table: file_content
+				.lines()
+				.filter_map(parse)
+				.collect::<HashMap<_, _>>()
+				.into()
+		}
+	}
+}
+
+impl Ratings {
+	const FILE_PATH: &'static str = "ratings.csv";
+
+	///every participant is rated against every other participant who isn't on their team
+	#[expect(clippy::significant_drop_tightening, reason = "the lock has to be held until the file is written")]
+	pub async fn record(&self, mode: &str, placements: &[Placement]) {
+		let rated = placements
+			.iter()
+			.filter_map(|placement| {
+				let key = Key {
+					player: placement.name.clone(),
+					mode: mode.to_owned(),
+					class: class_name(placement.class)?
+				};
+				Some((key, placement))
+			})
+			.collect::<Vec<_>>();
+
+		let mut table = self.table.write().await;
+		let before = rated
+			.iter()
+			.map(|(key, _)| table.get(key).copied().unwrap_or_default())
+			.collect::<Vec<_>>();
+		let rated_placements = rated
+			.iter()
+			.map(|(_, placement)| *placement)
+			.collect::<Vec<_>>();
+
+		for ((key, _), after) in rated.iter().zip(rate(&before, &rated_placements)) {
+			if let Some(rating) = after {
+				table.insert(key.clone(), rating);
+			}
+		}
+
+		//keeps other writers out until the file is written, so an older table can't overwrite a newer one
+		let table = table.downgrade();
+		let file_content = table
+			.iter()
+			.map(|(key, rating)| format!("{};{};{};{};{}", escape_field(&key.player), key.mode, key.class, rating.value, rating.games))
+			.collect::<Vec<_>>()
+			.join("\n");
+
+		if let Err(error) = tokio_fs::write(Self::FILE_PATH, file_content).await {
+			red_ln!("failed to write {} - {}", Self::FILE_PATH, error);
+		}
+	}
+
+	///(mode, class, rating) for each combination `player` has played, best first
+	pub async fn of(&self, player: &str) -> Vec<(String, &'static str, Rating)> {
+		let mut ratings = self.table
+			.read().await
+			.iter()
+			.filter(|(key, _)| key.player == player)
+			.map(|(key, rating)| (key.mode.clone(), key.class, *rating))
+			.collect::<Vec<_>>();
+
+		ratings.sort_by(|(_, _, lhs), (_, _, rhs)| rhs.value.total_cmp(&lhs.value));
+		ratings
+	}
+
+	///(player, class, rating) of the best players in `mode`, optionally only of one class
+	pub async fn top(&self, mode: &str, class: Option<&str>, limit: usize) -> Vec<(String, &'static str, Rating)> {
+		let mut ratings = self.table
+			.read().await
+			.iter()
+			.filter(|(key, _)| key.mode == mode && class.is_none_or(|class| key.class == class))
+			.map(|(key, rating)| (key.player.clone(), key.class, *rating))
+			.collect::<Vec<_>>();
+
+		ratings.sort_by(|(_, _, lhs), (_, _, rhs)| rhs.value.total_cmp(&lhs.value));
+		ratings.truncate(limit);
+		ratings
+	}
+}
+
+pub fn post_leaderboards_periodically(server: &Server) {
+	let server_static = server.extend_lifetime();
+
+	tokio::spawn(async move {
+		loop {
+			sleep(LEADERBOARD_INTERVAL).await;
+
+			for mode in modes() {
+				let top = server_static.addons.ratings.top(mode, None, LEADERBOARD_SIZE).await;
+				if top.is_empty() {
+					continue;
+				}
+
+				let leaderboard = format_leaderboard(&top).join("\n");
+				server_static.addons.discord_integration.post(&format!("**{mode} leaderboard**\n{leaderboard}"), false).await;
+			}
+		}
+	});
+}
+
+///everything ratings are kept for, as accepted by `/top`
+pub fn modes() -> impl Iterator<Item = &'static str> {
+	iter::once(DUEL).chain(Mode::ALL.map(Mode::name))
+}
+
+pub fn class_name(class: CombatClass) -> Option<&'static str> {
+	CLASSES
+		.iter()
+		.find(|(combat_class, _)| *combat_class == class)
+		.map(|(_, name)| *name)
+}
+
+pub fn parse_class(name: &str) -> Option<&'static str> {
+	CLASSES
+		.iter()
+		.find(|(_, class_name)| class_name.eq_ignore_ascii_case(name))
+		.map(|(_, class_name)| *class_name)
+}
+
+pub fn format_leaderboard(top: &[(String, &'static str, Rating)]) -> Vec<String> {
+	top
+		.iter()
+		.enumerate()
+		.map(|(rank, (player, class, rating))| format!("{}. {player} ({class}) {:.0}", rank + 1, rating.value))
+		.collect()
+}
+
+///the ratings after a game, `None` for those without opponents
+pub fn rate(before: &[Rating], placements: &[&Placement]) -> Vec<Option<Rating>> {
+	placements
+		.iter()
+		.enumerate()
+		.map(|(index, placement)| {
+			let mut opponents = 0_u16;
+			let mut balance = 0.0;
+			for (other_index, other) in placements.iter().enumerate() {
+				if other_index == index || placement.team.is_some() && placement.team == other.team {
+					continue;
+				}
+
+				let actual = match placement.score.cmp(&other.score) {
+					Ordering::Greater => 1.0,
+					Ordering::Equal   => 0.5,
+					Ordering::Less    => 0.0
+				};
+				balance += actual - expected_score(before[index].value, before[other_index].value);
+				opponents += 1;
+			}
+
+			if opponents == 0 {
+				return None;
+			}
+
+			let k_factor = if before[index].games < PROVISIONAL_GAMES { PROVISIONAL_K_FACTOR } else { K_FACTOR };
+			Some(Rating {
+				value: k_factor.mul_add(balance / f32::from(opponents), before[index].value),
+				games: before[index].games + 1
+			})
+		})
+		.collect()
+}
+
+pub fn expected_score(own: f32, other: f32) -> f32 {
+	1.0 / (1.0 + 10.0_f32.powf((other - own) / 400.0))
+}
+
+fn parse(line: &str) -> Option<(Key, Rating)> {
+	let [player, mode, class, value, games]: [String; 5] = split_fields(line)
+		.try_into()
+		.ok()?;
+
+	let key = Key {
+		player,
+		class: parse_class(&class)?,
+		mode
+	};
+	let rating = Rating {
+		value: value.parse().ok()?,
+		games: games.parse().ok()?
+	};
+
+	Some((key, rating))
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use protocol::utils::{maximum_experience_of, power_of};
+
+use crate::server::player::Player;
+use crate::server::Server;
+use crate::server::utils::give_xp;
+
+///grants experience for a kill, split evenly between the killer and assisting teammates
+pub async fn reward_kill(server: &Server, killer: &Player, victim: &Player, assisters: &[Arc<Player>]) {
+	let (killer_level, victim_level, victim_name) = {
+		let killer_character = killer.character.read().await;
+		let victim_character = victim.character.read().await;
+		(killer_character.level, victim_character.level, victim_character.name.clone())
+	};
+
+	let config = &server.config;
+	let level_factor = (power_of(victim_level) as f32 / power_of(killer_level).max(1) as f32)
+		.clamp(config.reward_min_level_factor, config.reward_max_level_factor);
+
+	let mut addon_data = killer.addon_data.write().await;
+	let recent_victims = &mut addon_data.kill_data.recent_victims;
+	recent_victims.retain(|(_, timestamp)| timestamp.elapsed() < config.reward_farming_window);
+	let repeat_kills = recent_victims.iter().filter(|(name, _)| *name == victim_name).count();
+	recent_victims.push((victim_name, Instant::now()));
+	let killer_team = addon_data.team;
+	drop(addon_data);
+
+	let repeat_factor = config.reward_repeat_kill_factor.powi(repeat_kills as i32);
+	let total_reward = maximum_experience_of(killer_level) as f32 * config.reward_base * level_factor * repeat_factor;
+
+	let mut recipients = vec![killer];
+	for assister in assisters {
+		if killer_team.is_some() && assister.addon_data.read().await.team == killer_team {
+			recipients.push(assister);
+		}
+	}
+
+	let share = (total_reward / recipients.len() as f32) as i32;
+	if share <= 0 {
+		return;
+	}
+	for recipient in recipients {
+		give_xp(server, recipient, share).await;
+	}
+}
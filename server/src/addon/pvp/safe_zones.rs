@@ -0,0 +1,171 @@
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::mem;
+
+use colour::red_ln;
+use tokio::sync::RwLock;
+
+use protocol::nalgebra::{Point2, Point3, Vector2};
+use protocol::packet::CreatureUpdate;
+use protocol::utils::constants::{SIZE_BLOCK, SIZE_ZONE};
+
+use crate::addon::pvp::refresh_hostility;
+use crate::server::player::Player;
+use crate::server::Server;
+use crate::server::spatial_index::zone_of;
+
+///axis-aligned box in world coordinates in which players can't hurt each other
+#[derive(Debug, Clone)]
+pub struct Region {
+	pub name: String,
+	pub min: Point3<i64>,
+	pub max: Point3<i64>
+}
+
+impl Region {
+	pub fn contains(&self, position: Point3<i64>) -> bool {
+		(0..3).all(|axis| (self.min[axis]..=self.max[axis]).contains(&position[axis]))
+	}
+
+	pub fn parse(line: &str) -> Option<Self> {
+		let [name, coordinates @ ..]: [&str; 7] = line
+			.split(';')
+			.collect::<Vec<_>>()
+			.try_into()
+			.ok()?;
+
+		let [min_x, min_y, min_z, max_x, max_y, max_z] = coordinates.map(str::parse::<i64>);
+		Some(Self {
+			name: name.to_owned(),
+			min: Point3::new(min_x.ok()?, min_y.ok()?, min_z.ok()?),
+			max: Point3::new(max_x.ok()?, max_y.ok()?, max_z.ok()?)
+		})
+	}
+
+	pub fn serialize(&self) -> String {
+		format!("{};{};{};{};{};{};{}", self.name, self.min.x, self.min.y, self.min.z, self.max.x, self.max.y, self.max.z)
+	}
+}
+
+///pvp-free regions, editable with `/region`
+#[derive(Debug)]
+pub struct Regions {
+	list: RwLock<Vec<Region>>
+}
+
+impl Default for Regions {
+	fn default() -> Self {
+		let file_content = match fs::read_to_string(Self::FILE_PATH) {
+			Ok(content) => content,
+			Err(error) if error.kind() == NotFound => String::new(),
+			Err(error) => panic!("failed to load {} - {}", Self::FILE_PATH, error)
+		};
+
+		Self {
+			list: file_content
+				.lines()
+				.filter_map(Region::parse)
+				.collect::<Vec<_>>()
+				.into()
+		}
+	}
+}
+
+impl Regions {
+	const FILE_PATH: &'static str = "safe_regions.csv";
+
+	///name of the region `position` lies in, if any
+	pub async fn at(&self, position: Point3<i64>) -> Option<String> {
+		self.list
+			.read().await
+			.iter()
+			.find(|region| region.contains(position))
+			.map(|region| region.name.clone())
+	}
+
+	///a box reaching `radius` blocks in every horizontal direction
+	pub async fn add_around(&self, name: &str, center: Point3<i64>, radius: i64) -> Result<(), &'static str> {
+		let extent = Vector2::new(radius * SIZE_BLOCK, radius * SIZE_BLOCK);
+		self.add(name, center.xy() - extent, center.xy() + extent).await
+	}
+
+	///the whole zone (map tile) `position` lies in
+	pub async fn add_zone(&self, name: &str, position: Point3<i64>) -> Result<(), &'static str> {
+		let corner = zone_of(position).map(i64::from) * SIZE_ZONE;
+		self.add(name, corner, corner + Vector2::repeat(SIZE_ZONE - 1)).await
+	}
+
+	pub async fn remove(&self, name: &str) -> Result<(), &'static str> {
+		let mut list = self.list.write().await;
+		let count_before = list.len();
+		list.retain(|region| region.name != name);
+		if list.len() == count_before {
+			return Err("no region with that name");
+		}
+
+		save(&list);
+		drop(list);
+		Ok(())
+	}
+
+	pub async fn names(&self) -> Vec<String> {
+		self.list
+			.read().await
+			.iter()
+			.map(|region| region.name.clone())
+			.collect()
+	}
+
+	///the region spans from the bottom to the top of the world
+	async fn add(&self, name: &str, min: Point2<i64>, max: Point2<i64>) -> Result<(), &'static str> {
+		if name.contains(';') {
+			return Err("region names can't contain ';'");
+		}
+
+		let mut list = self.list.write().await;
+		if list.iter().any(|region| region.name == name) {
+			return Err("a region with that name exists already");
+		}
+		list.push(Region {
+			name: name.to_owned(),
+			min: Point3::new(min.x, min.y, i64::MIN),
+			max: Point3::new(max.x, max.y, i64::MAX)
+		});
+
+		save(&list);
+		drop(list);
+		Ok(())
+	}
+}
+
+///tells players when they enter or leave a region and updates who they can attack
+pub async fn on_creature_update(server: &Server, source: &Player, packet: &CreatureUpdate) {
+	let Some(position) = packet.position
+		else { return; };
+
+	let region = server.addons.safe_zones.at(position).await;
+	let mut addon_data = source.addon_data.write().await;
+	if addon_data.safe_zone == region {
+		return;
+	}
+	let previous_region = mem::replace(&mut addon_data.safe_zone, region.clone());
+	drop(addon_data);
+
+	match region {
+		Some(name) => source.notify(format!("you entered {name}, pvp is disabled here")).await,
+		None => source.notify(format!("you left {}, pvp is enabled again", previous_region.unwrap_or_default())).await
+	}
+	refresh_hostility(server, source).await;
+}
+
+fn save(list: &[Region]) {
+	let file_content = list
+		.iter()
+		.map(Region::serialize)
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	if let Err(error) = fs::write(Regions::FILE_PATH, file_content) {
+		red_ln!("failed to write {} - {}", Regions::FILE_PATH, error);
+	}
+}
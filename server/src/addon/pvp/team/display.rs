@@ -8,13 +8,7 @@ use protocol::packet::creature_update::{Affiliation, Appearance};
 use protocol::packet::common::CreatureId;
 use tap::Pipe;
 
-use crate::server::{Server, player::Player, creature_id_pool::CreatureIdPool};
-
-pub fn reserve_dummy_ids(pool: &mut CreatureIdPool) {
-	for _ in 1..=3 {
-		pool.claim();
-	}
-}
+use crate::server::{Server, player::Player, creature_id_pool::IdRange};
 
 pub async fn reload_for_all_members(server: &Server, team: i32) {
 	let members = super::get_members(server, team).await;
@@ -80,7 +74,7 @@ pub async fn update_for_all_members(packet: &CreatureUpdate, source: &Player, me
 
 			let future = async move {
 				let display_update = create_display_update(packet, id);
-				recipient.send_ignoring(&display_update).await;
+				recipient.enqueue(&display_update).await;
 			};
 
 			Some(future)
@@ -127,7 +121,7 @@ fn get_displayed_members<'team>(members: &'team [Arc<Player>], pov: &Player) ->
 		.chain(iter::repeat(None))
 		.take(3)
 		.enumerate()
-		.map(|(i, member)| (CreatureId(i as i64 + 1), member))
+		.map(|(i, member)| (IdRange::HudDummies.nth(i), member))
 		.collect::<Vec<_>>()
 		.try_into()
 		.unwrap()
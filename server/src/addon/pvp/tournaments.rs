@@ -0,0 +1,354 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fmt;
+
+use tokio::sync::RwLock;
+
+use crate::addon::pvp::ratings::{DUEL, Rating};
+use crate::server::player::Player;
+use crate::server::Server;
+
+///tournament matches are played as duels in the arena
+const BEST_OF: u32 = 3;
+const MIN_ENTRANTS: usize = 2;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+	SingleElimination,
+	DoubleElimination
+}
+
+impl Format {
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name {
+			"single" => Some(Self::SingleElimination),
+			"double" => Some(Self::DoubleElimination),
+			_ => None
+		}
+	}
+
+	///entrants are out once they lost this many matches
+	const fn losses_allowed(self) -> u32 {
+		match self {
+			Self::SingleElimination => 1,
+			Self::DoubleElimination => 2
+		}
+	}
+}
+
+#[derive(Debug)]
+pub struct Entrant {
+	pub name: String,
+	///lower is better, determined by rating when the bracket gets generated
+	pub seed: usize,
+	pub losses: u32
+}
+
+#[derive(Debug)]
+pub struct Pairing {
+	pub names: [String; 2],
+	pub winner: Option<usize>,
+	///only matches started through `/bracket call` count, casual duels between the same players don't
+	pub called: bool
+}
+
+#[derive(Debug)]
+pub struct Tournament {
+	pub format: Format,
+	pub entrants: Vec<Entrant>,
+	///0 while signups are open
+	pub round: u32,
+	pub pairings: Vec<Pairing>,
+	pub byes: Vec<String>
+}
+
+///admin driven elimination brackets, whose matches are played as duels
+#[derive(Debug, Default)]
+pub struct TournamentManager {
+	current: RwLock<Option<Tournament>>
+}
+
+impl TournamentManager {
+	pub async fn open(&self, server: &Server, format: Format) -> Result<(), &'static str> {
+		let mut current = self.current.write().await;
+		if current.is_some() {
+			return Err("a tournament is in progress already");
+		}
+
+		*current = Some(Tournament {
+			format,
+			entrants: vec![],
+			round: 0,
+			pairings: vec![],
+			byes: vec![]
+		});
+		drop(current);
+
+		server.announce(format!("signups for a {format} tournament are open, use /tournament join")).await;
+		Ok(())
+	}
+
+	pub async fn join(&self, player: &Player) -> Result<(), &'static str> {
+		let name = player.character.read().await.name.clone();
+		let mut current = self.current.write().await;
+		let tournament = current.as_mut().ok_or("no tournament is open")?;
+
+		if tournament.round > 0 {
+			return Err("signups are closed");
+		}
+		if tournament.entrants.iter().any(|entrant| entrant.name == name) {
+			return Err("you signed up already");
+		}
+
+		tournament.entrants.push(Entrant { name, seed: 0, losses: 0 });
+		drop(current);
+		Ok(())
+	}
+
+	pub async fn leave(&self, player: &Player) -> Result<(), &'static str> {
+		let name = player.character.read().await.name.clone();
+		let mut current = self.current.write().await;
+		let tournament = current.as_mut().ok_or("no tournament is open")?;
+
+		if tournament.round > 0 {
+			return Err("the tournament has started, your remaining matches will be forfeited if you don't show up");
+		}
+		tournament.entrants.retain(|entrant| entrant.name != name);
+		drop(current);
+		Ok(())
+	}
+
+	///closes signups, seeds everyone by their best duel rating and generates the first round
+	pub async fn start(&self, server: &Server) -> Result<(), &'static str> {
+		let mut current = self.current.write().await;
+		let tournament = current.as_mut().ok_or("no tournament is open")?;
+
+		if tournament.round > 0 {
+			return Err("the tournament has started already");
+		}
+		if tournament.entrants.len() < MIN_ENTRANTS {
+			return Err("not enough entrants");
+		}
+
+		let mut ratings = vec![];
+		for entrant in &tournament.entrants {
+			let best = server.addons.ratings
+				.of(&entrant.name).await
+				.into_iter()
+				.find(|(mode, _, _)| mode == DUEL)
+				.map_or_else(Rating::default, |(_, _, rating)| rating);
+			ratings.push(best.value);
+		}
+		let mut seeding = tournament.entrants.drain(..).zip(ratings).collect::<Vec<_>>();
+		seeding.sort_by(|(_, lhs), (_, rhs)| rhs.total_cmp(lhs));
+		tournament.entrants = seeding
+			.into_iter()
+			.enumerate()
+			.map(|(seed, (entrant, _))| Entrant { seed, ..entrant })
+			.collect();
+
+		tournament.next_round();
+		let bracket = tournament.bracket();
+		drop(current);
+
+		server.announce(bracket).await;
+		Ok(())
+	}
+
+	///starts the next match that hasn't been played yet
+	pub async fn call(&self, server: &Server) -> Result<String, &'static str> {
+		let mut current = self.current.write().await;
+		let tournament = current.as_mut().ok_or("no tournament is open")?;
+
+		let pairing = tournament.pairings
+			.iter_mut()
+			.find(|pairing| pairing.winner.is_none() && !pairing.called)
+			.ok_or("every match of this round has been called already")?;
+
+		let [first, second] = &pairing.names;
+		let first_player = server.find_player(first).await.ok_or("the first player isn't online, use /bracket win to record a walkover")?;
+		let second_player = server.find_player(second).await.ok_or("the second player isn't online, use /bracket win to record a walkover")?;
+		server.addons.duels.start(server, &first_player, &second_player, BEST_OF, true).await?;
+		pairing.called = true;
+
+		let announcement = format!("tournament match: {first} vs {second} (best of {BEST_OF})");
+		drop(current);
+
+		server.announce(announcement.clone()).await;
+		Ok(announcement)
+	}
+
+	///lets `winner` advance without playing, for when the opponent doesn't show up
+	pub async fn record_walkover(&self, server: &Server, winner: &str) -> Result<(), &'static str> {
+		let mut current = self.current.write().await;
+		let tournament = current.as_mut().ok_or("no tournament is open")?;
+
+		let (pairing, index) = tournament.pairings
+			.iter_mut()
+			.filter(|pairing| pairing.winner.is_none())
+			.find_map(|pairing| {
+				let index = pairing.names.iter().position(|name| name.eq_ignore_ascii_case(winner))?;
+				Some((pairing, index))
+			})
+			.ok_or("that player has no open match")?;
+		pairing.winner = Some(index);
+
+		let announcements = tournament.advance();
+		if tournament.is_over() {
+			*current = None;
+		}
+		drop(current);
+
+		for announcement in announcements {
+			server.announce(announcement).await;
+		}
+		Ok(())
+	}
+
+	pub async fn on_duel_result(&self, server: &Server, winner: &str, loser: &str) {
+		let mut current = self.current.write().await;
+		let Some(tournament) = current.as_mut()
+			else { return; };
+
+		let Some(pairing) = tournament.pairings
+			.iter_mut()
+			.find(|pairing| pairing.called && pairing.winner.is_none() && [winner, loser].iter().all(|name| pairing.names.iter().any(|entrant| entrant == name)))
+			else { return; }; //not a tournament match
+
+		pairing.winner = pairing.names.iter().position(|name| name == winner);
+
+		let announcements = tournament.advance();
+		if tournament.is_over() {
+			*current = None;
+		}
+		drop(current);
+
+		for announcement in announcements {
+			server.announce(announcement).await;
+		}
+	}
+
+	pub async fn cancel(&self, server: &Server) -> Result<(), &'static str> {
+		self.current.write().await.take().ok_or("no tournament is open")?;
+		server.announce("the tournament has been cancelled").await;
+		Ok(())
+	}
+
+	#[expect(clippy::significant_drop_tightening, reason = "cannot drop any earlier")]
+	pub async fn status(&self) -> String {
+		let current = self.current.read().await;
+		let Some(tournament) = current.as_ref()
+			else { return "no tournament is open".to_owned(); };
+
+		if tournament.round == 0 {
+			let names = tournament.entrants
+				.iter()
+				.map(|entrant| entrant.name.as_str())
+				.collect::<Vec<_>>()
+				.join(", ");
+			return format!("{} tournament, signups open: {names}", tournament.format);
+		}
+		tournament.bracket()
+	}
+}
+
+impl Tournament {
+	///once every match of the current round has a winner, the losers are charged and the next round is generated
+	fn advance(&mut self) -> Vec<String> {
+		if self.pairings.iter().any(|pairing| pairing.winner.is_none()) {
+			return vec![];
+		}
+
+		for pairing in &self.pairings {
+			let Some(winner) = pairing.winner
+				else { continue; };
+			let loser = &pairing.names[1 - winner];
+			if let Some(entrant) = self.entrants.iter_mut().find(|entrant| entrant.name == *loser) {
+				entrant.losses += 1;
+			}
+		}
+
+		self.next_round();
+		if let Some(champion) = self.champion() {
+			return vec![format!("{champion} won the tournament!")];
+		}
+		vec![self.bracket()]
+	}
+
+	///pairs the best remaining seed with the worst one among all entrants with the same number of losses.
+	///in double elimination this naturally forms a winners and a losers bracket, which meet in the grand final
+	pub fn next_round(&mut self) {
+		self.round += 1;
+		self.pairings.clear();
+		self.byes.clear();
+
+		let losses_allowed = self.format.losses_allowed();
+		let mut alive = self.entrants
+			.iter()
+			.filter(|entrant| entrant.losses < losses_allowed)
+			.collect::<Vec<_>>();
+		alive.sort_by_key(|entrant| entrant.seed);
+
+		if alive.len() < 2 {
+			return;
+		}
+		if let [first, second] = alive[..] {
+			self.pairings.push(Pairing { names: [first.name.clone(), second.name.clone()], winner: None, called: false });
+			return;
+		}
+
+		let mut brackets = BTreeMap::<u32, Vec<&Entrant>>::new();
+		for entrant in alive {
+			brackets.entry(entrant.losses).or_default().push(entrant);
+		}
+
+		for mut bracket in brackets.into_values() {
+			while bracket.len() >= 2 {
+				let best = bracket.remove(0);
+				let worst = bracket.pop().unwrap();
+				self.pairings.push(Pairing { names: [best.name.clone(), worst.name.clone()], winner: None, called: false });
+			}
+			if let Some(waiting) = bracket.pop() {
+				self.byes.push(waiting.name.clone());
+			}
+		}
+	}
+
+	fn is_over(&self) -> bool {
+		self.round > 0 && self.pairings.is_empty()
+	}
+
+	fn champion(&self) -> Option<&str> {
+		if !self.is_over() {
+			return None;
+		}
+
+		self.entrants
+			.iter()
+			.find(|entrant| entrant.losses < self.format.losses_allowed())
+			.map(|entrant| entrant.name.as_str())
+	}
+
+	fn bracket(&self) -> String {
+		let mut lines = self.pairings
+			.iter()
+			.map(|pairing| {
+				let [first, second] = &pairing.names;
+				let result = pairing.winner.map_or_else(String::new, |winner| format!(" ({} won)", pairing.names[winner]));
+				format!("{first} vs {second}{result}")
+			})
+			.collect::<Vec<_>>();
+		lines.extend(self.byes.iter().map(|name| format!("{name} (bye)")));
+
+		format!("tournament round {}: {}", self.round, lines.join(", "))
+	}
+}
+
+impl Display for Format {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::SingleElimination => "single elimination",
+			Self::DoubleElimination => "double elimination"
+		})
+	}
+}
@@ -0,0 +1,125 @@
+use std::time::{Duration, Instant};
+
+use protocol::nalgebra::{Point3, Vector3};
+use protocol::packet::common::CreatureId;
+use protocol::utils::constants::SIZE_BLOCK;
+
+use crate::addon::pvp::map_head;
+use crate::server::despawn;
+use crate::server::player::Player;
+use crate::server::Server;
+
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(2);
+///spectators hover above their target to keep out of the way
+const FOLLOW_OFFSET: Vector3<i64> = Vector3::new(0, 0, SIZE_BLOCK * 6);
+
+///kept in the [`AddonData`](crate::server::player::addon_data::AddonData) of spectators
+#[derive(Debug, Clone, Copy)]
+pub struct Spectating {
+	target: CreatureId,
+	///where the spectator gets sent back to once they stop
+	origin: Point3<i64>,
+	last_follow: Instant
+}
+
+pub async fn is_spectating(player: &Player) -> bool {
+	player.addon_data.read().await.spectating.is_some()
+}
+
+///hides `spectator` from everyone and starts following `target`. switching targets keeps the original position
+pub async fn start(server: &Server, spectator: &Player, target: &Player) -> Result<(), &'static str> {
+	if spectator.id == target.id {
+		return Err("you can't spectate yourself");
+	}
+	if target.addon_data.read().await.spectating.is_some() {
+		return Err("that player is spectating as well");
+	}
+
+	let mut addon_data = spectator.addon_data.write().await;
+	if addon_data.duel.is_some() {
+		return Err("you can't spectate during a duel");
+	}
+	let was_hidden = addon_data.spectating.is_some();
+	let origin = match addon_data.spectating {
+		Some(spectating) => spectating.origin,
+		None => spectator.character.read().await.position
+	};
+	addon_data.spectating = Some(Spectating {
+		target: target.id,
+		origin,
+		last_follow: Instant::now()
+	});
+	drop(addon_data);
+
+	if !was_hidden {
+		server.broadcast_batched(&despawn(spectator.id), Some(spectator)).await;
+		server.broadcast_batched(&map_head::create_toggle_packet(spectator, false), Some(spectator)).await;
+	}
+	follow(server, spectator, target).await;
+	Ok(())
+}
+
+///makes `spectator` visible again where they started spectating
+pub async fn stop(server: &Server, spectator: &Player) -> Result<(), &'static str> {
+	let spectating = spectator
+		.addon_data
+		.write().await
+		.spectating
+		.take()
+		.ok_or("you aren't spectating anyone")?;
+
+	let server_static = server.extend_lifetime();
+	let spectator_id = spectator.id;
+	tokio::spawn(async move {
+		//the teleport resends everyone in range, which reveals the spectator again
+		if let Some(player) = server_static.find_player_by_id(spectator_id).await {
+			server_static.teleport(&player, spectating.origin).await;
+		}
+	});
+	Ok(())
+}
+
+pub async fn target_of(spectator: &Player) -> Option<CreatureId> {
+	spectator
+		.addon_data
+		.read().await
+		.spectating
+		.map(|spectating| spectating.target)
+}
+
+pub async fn on_tick(server: &Server) {
+	let players = server.players.read().await.clone();
+
+	for player in &players {
+		let mut addon_data = player.addon_data.write().await;
+		let Some(ref mut spectating) = addon_data.spectating
+			else { continue; };
+		if spectating.last_follow.elapsed() < FOLLOW_INTERVAL {
+			continue;
+		}
+		spectating.last_follow = Instant::now();
+		let target_id = spectating.target;
+		drop(addon_data);
+
+		match players.iter().find(|target| target.id == target_id) {
+			Some(target) => follow(server, player, target).await,
+			None => {
+				if stop(server, player).await.is_ok() {
+					player.notify("the player you were spectating left").await;
+				}
+			}
+		}
+	}
+}
+
+async fn follow(server: &Server, spectator: &Player, target: &Player) {
+	let destination = target.character.read().await.position + FOLLOW_OFFSET;
+
+	let server_static = server.extend_lifetime();
+	let spectator_id = spectator.id;
+	tokio::spawn(async move {
+		if let Some(player) = server_static.find_player_by_id(spectator_id).await {
+			server_static.teleport(&player, destination).await;
+		}
+	});
+}
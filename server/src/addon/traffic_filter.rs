@@ -1,8 +1,10 @@
-use protocol::packet::creature_update::CreatureFlag;
+use protocol::packet::creature_update::{Animation, CreatureFlag, PhysicsFlag};
 use protocol::packet::CreatureUpdate;
+use protocol::utils::constants::SIZE_BLOCK;
 
 use crate::server::creature::Creature;
 
+///strips everything that doesn't need to be relayed, based on the previous state of the sender. returns whether any data is remaining
 pub fn filter(packet: &mut CreatureUpdate, former_state: &Creature, updated_state: &Creature) -> bool {
 	packet.rotation       = None;//this would be useful if it worked as intended, but unfortunately it has no effect
 	packet.head_tilt      = None;
@@ -32,10 +34,12 @@ pub fn filter(packet: &mut CreatureUpdate, former_state: &Creature, updated_stat
 	//- rarity
 	//- skilltree //need this because receiving clients locally remove the glider if the skill isnt learned
 
-	//todo:
+	//per recipient, see compress:
 	//- position
 	//- velocity
 	//- acceleration
+
+	//todo:
 	//- combo
 	//- showPatchtime
 	//- manaCharge
@@ -48,35 +52,6 @@ pub fn filter(packet: &mut CreatureUpdate, former_state: &Creature, updated_stat
 	//- unknown38
 	//- unknown42
 
-//	let need_velocity_z = packet.velocity.map_or(false, |velocity| {
-//		if updated_state.flags.get(CreatureFlag::Climbing) {
-//			false
-//		} else if updated_state.flags_physics.get(PhysicsFlag::Swimming) {
-//			velocity.z > 1.0 && velocity.z - (updated_state.acceleration.z / 80.0 * 12.0) > 1.0 //wip
-//		} else if velocity.z < former_state.velocity.z {
-//			false
-//		} else if updated_state.flags_physics.get(PhysicsFlag::OnGround) {
-//			velocity.z > 0.0
-//		} else { //airborne
-//			true
-//		}
-//	});
-//	let glider_hovering = need_velocity_z && updated_state.flags.get(CreatureFlag::Gliding);
-//	let movement_changed = packet.acceleration.map_or(false, |acceleration| acceleration.metric_distance(&former_state.acceleration) > 0f32);//todo: compare to last sent (4)
-//	let teleported = packet.position.map_or(false, |position| distance::<f64, 3>(&position.cast(), &former_state.position.cast()) > SIZE_BLOCK as f64 * 4.0);
-//	let dodge_started = packet.effect_time_dodge.map_or(false, |effect_time_dodge| effect_time_dodge > former_state.effect_time_dodge);
-//	let intercepting = updated_state.animation == Animation::Intercept;
-
-//	if !movement_changed {
-//		packet.acceleration = None;
-//		if !glider_hovering && !teleported {
-//			packet.position = None;
-//		}
-//	}
-//	if !need_velocity_z && !dodge_started && !intercepting {
-//		packet.velocity = None;
-//	}
-
 	let new_animation_started = updated_state.animation_time < former_state.animation_time;
 
 	packet.animation_time.filter_in_place(|_| new_animation_started);
@@ -104,6 +79,44 @@ pub fn filter(packet: &mut CreatureUpdate, former_state: &Creature, updated_stat
 
 	packet.aim_offset.filter_in_place(|_| updated_state.flags.get(CreatureFlag::Aiming));//todo: compare to last sent (2)
 
+	has_data(packet)
+}
+
+///strips movement data that the recipient can extrapolate from the state it was sent last
+pub fn compress(packet: &mut CreatureUpdate, last_sent: &Creature, current_state: &Creature) -> bool {
+	let need_velocity_z = packet.velocity.is_some_and(|velocity| {
+		if current_state.flags.get(CreatureFlag::Climbing) {
+			false
+		} else if current_state.flags_physics.get(PhysicsFlag::Swimming) {
+			velocity.z > 1.0 && velocity.z - (current_state.acceleration.z / 80.0 * 12.0) > 1.0 //wip
+		} else if velocity.z < last_sent.velocity.z {
+			false
+		} else if current_state.flags_physics.get(PhysicsFlag::OnGround) {
+			velocity.z > 0.0
+		} else { //airborne
+			true
+		}
+	});
+	let glider_hovering = need_velocity_z && current_state.flags.get(CreatureFlag::Gliding);
+	let movement_changed = packet.acceleration.is_some_and(|acceleration| acceleration != last_sent.acceleration);
+	let teleported = packet.position.is_some_and(|position| (position - last_sent.position).cast::<f64>().norm() > SIZE_BLOCK as f64 * 4.0);
+	let dodge_started = packet.effect_time_dodge.is_some_and(|effect_time_dodge| effect_time_dodge > 0); //filter only lets resets through
+	let intercepting = current_state.animation == Animation::Intercept;
+
+	if !movement_changed {
+		packet.acceleration = None;
+		if !glider_hovering && !teleported {
+			packet.position = None;
+		}
+	}
+	if !need_velocity_z && !dodge_started && !intercepting {
+		packet.velocity = None;
+	}
+
+	has_data(packet)
+}
+
+const fn has_data(packet: &CreatureUpdate) -> bool {
 	//todo: macro
 	packet.position          .is_some() ||
 	packet.rotation          .is_some() ||
@@ -153,7 +166,6 @@ pub fn filter(packet: &mut CreatureUpdate, former_state: &Creature, updated_stat
 	packet.name              .is_some() ||
 	packet.skill_tree        .is_some() ||
 	packet.mana_cubes        .is_some()
-	//returns whether any data is remaining
 }
 
 trait FilterInPlace<T> {
@@ -15,6 +15,8 @@ use server::Server;
 
 mod server;
 mod addon;
+#[cfg(test)]
+mod tests;
 
 #[tokio::main]
 async fn main() {
@@ -1,19 +1,21 @@
 use std::collections::HashMap;
-use std::io::ErrorKind::{InvalidData, InvalidInput};
+use std::future::Future;
+use std::io::ErrorKind::{InvalidData, InvalidInput, TimedOut};
 use std::net::SocketAddr;
 use std::ptr;
 use std::sync::Arc;
 use std::time::Duration;
 
+use colour::yellow_ln;
 use futures::future::join_all;
-use tap::{Pipe, Tap};
+use tap::Pipe;
 use tokio::{io, select};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 use tokio::io::{BufReader, BufWriter};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::sync::RwLock;
-use tokio::time::sleep;
+use tokio::time::{Instant, sleep, timeout_at};
 
 use protocol::{Packet, WriteCwData};
 use protocol::nalgebra::{Point2, Point3};
@@ -24,42 +26,61 @@ use protocol::packet::creature_update::Affiliation;
 use protocol::packet::world_update::loot::GroundItem;
 use protocol::packet::world_update::Sound;
 use protocol::packet::world_update::sound::Kind::*;
-use protocol::utils::constants::SIZE_ZONE;
 use protocol::utils::io_extensions::{ReadPacket, WriteArbitrary, WritePacket};
 
 use crate::addon::{Addons, freeze_time, play_sound_for_everyone};
 use crate::addon::pvp::map_head;
 use crate::addon::pvp;
+use crate::server::config::Config;
 use crate::server::creature::Creature;
-use crate::server::creature_id_pool::CreatureIdPool;
+use crate::server::creature_id_pool::{CreatureIdPool, IdRange};
 use crate::server::handle_packet::HandlePacket;
+use crate::server::outbox::{Enqueue, Outbox};
 use crate::server::player::Player;
+use crate::server::spatial_index::{SpatialIndex, zone_of};
 
 pub mod creature_id_pool;
 pub mod player;
 mod handle_packet;
 pub mod creature;
 pub mod utils;
+pub mod outbox;
+mod tick;
+pub mod spatial_index;
+pub mod config;
 
-#[derive(Default)]
 pub struct Server {
-	id_pool: RwLock<CreatureIdPool>,
+	pub config: Config,
+	pub id_pool: CreatureIdPool,
 	pub players: RwLock<Vec<Arc<Player>>>,
 	loot: RwLock<HashMap<Point2<i32>, Vec<GroundItem>>>,
+	spatial_index: RwLock<SpatialIndex>,
 	pub addons: Addons
 }
 
+impl Default for Server {
+	fn default() -> Self {
+		let config = Config::default();
+
+		Self {
+			id_pool: CreatureIdPool::default(),
+			players: RwLock::default(),
+			loot: RwLock::default(),
+			spatial_index: RwLock::new(SpatialIndex::new(config.interest_radius)),
+			addons: Addons::default(),
+			config
+		}
+	}
+}
+
 impl Server {
 	pub async fn run(self) -> ! {
-		let mut id_pool = self.id_pool.write().await;
-		let _ = id_pool.claim(); //reserve 0 for the server itself
-		pvp::team::display::reserve_dummy_ids(&mut id_pool);
-		drop(id_pool);
-
 		let listener = TcpListener::bind("0.0.0.0:12345").await.expect("unable to bind listening socket");
 
 		self.addons.discord_integration.run(&self);
 		freeze_time(&self);
+		pvp::ratings::post_leaderboards_periodically(&self);
+		self.start_ticking();
 
 		loop {
 			let (stream, address) = listener.accept().await.unwrap();
@@ -79,45 +100,58 @@ impl Server {
 		let (mut reader, mut writer) = split_and_buffer(stream);
 
 		check_version(&mut reader, &mut writer).await?;
+		if self.addons.anti_cheat.is_banned(address.ip()).await {
+			writer.write_packet(&ConnectionRejection).await?;
+			return Err(io::Error::other("temporarily banned"));
+		}
 		writer.write_packet(&ConnectionAcceptance).await?;
 
-		let assigned_id = self.id_pool.write().await.claim();
-		write_abnormal_creature_update(&mut writer, assigned_id).await?;
+		let (Some(assigned_id), Some(map_head_id)) = (self.id_pool.claim(IdRange::Players), self.id_pool.claim(IdRange::MapHeads))
+			else {
+				writer.write_packet(&ConnectionRejection).await?;
+				return Err(io::Error::other("server is full"));
+			};
+		write_abnormal_creature_update(&mut writer, assigned_id.id()).await?;
 
 		let (initial_creature_update, character) = read_character_data(&mut reader).await?;
 
 		let (new_player, kick_receiver) = Player::new(
 			address,
 			assigned_id,
+			map_head_id,
 			character,
 			writer,
 		);
 		let player = Arc::new(new_player);
+		if let Err(reason) = Box::pin(self.addons.anti_cheat.admit(self, &player)).await { //boxed to keep this future's size in check
+			return Err(io::Error::other(reason));
+		}
 		self.players.write().await.push(Arc::clone(&player));
 		self.announce(format!("[+] {}", player.character.read().await.name)).await;
 		play_sound_for_everyone(self, MenuOpen2, 2.0, 1.0).await;
 
-		self.handle_packet(&player, initial_creature_update).await;
+		Box::pin(self.handle_packet(&player, initial_creature_update)).await; //boxed to keep this future's size in check
 
 		select! {
 			biased;
 			_ = kick_receiver => (),
-			() = self.handle_new_player(reader, &player) => ()
+			error = self.handle_new_player(reader, &player) => {
+				let name = player.character.read().await.name.clone();
+				yellow_ln!("{} disconnected: {}", name, error);
+			}
 		}
 
 		self.remove_player(&player).await;
-		self.id_pool.write().await.free(assigned_id);
 
 		Ok(())
 	}
 
-	async fn handle_new_player(&self, reader: BufReader<OwnedReadHalf>, player: &Player) {
+	async fn handle_new_player(&self, reader: BufReader<OwnedReadHalf>, player: &Player) -> io::Error {
 		player.send_ignoring(&MapSeed(56345)).await;
 		player.notify("welcome to berld").await;
 		send_existing_creatures(self, player).await;
 
 		self.read_packets_forever(player, reader).await
-			.expect_err("impossible");
 	}
 
 	pub async fn broadcast<Packet: FromServer>(&self, packet: &Packet, player_to_skip: Option<&Player>)
@@ -133,8 +167,22 @@ impl Server {
 			.await;
 	}
 
+	///like [`Server::broadcast`], but the packet gets queued and sent with the next tick
+	pub async fn broadcast_batched<Packet>(&self, packet: &Packet, player_to_skip: Option<&Player>)
+		where Outbox: Enqueue<Packet>
+	{
+		self.players
+			.read()
+			.await
+			.iter()
+			.filter(|player| !player_to_skip.is_some_and(|pts| ptr::eq(player.as_ref(), pts)))
+			.map(|player| player.enqueue(packet))
+			.pipe(join_all)
+			.await;
+	}
+
 	pub async fn add_drop(&self, item: Item, position: Point3<i64>, rotation: f32) {
-		let zone = position.xy().map(|scalar| (scalar / SIZE_ZONE) as i32);
+		let zone = zone_of(position);
 
 		let mut loot = self.loot.write().await;
 		let zone_loot = loot.entry(zone).or_insert(vec![]);
@@ -151,7 +199,7 @@ impl Server {
 		zone_loot_copy[zone_loot.len() - 1].droptime = 500;
 		drop(loot);
 
-		self.broadcast(&WorldUpdate {
+		self.broadcast_batched(&WorldUpdate {
 			loot: HashMap::from([(zone, zone_loot_copy)]),
 			sounds: vec![Sound::at(position, Drop)],
 			..Default::default()
@@ -160,7 +208,7 @@ impl Server {
 		let server_static = self.extend_lifetime();
 		tokio::spawn(async move {
 			sleep(Duration::from_millis(500)).await;
-			server_static.broadcast(&WorldUpdate::from(Sound::at(position, DropItem)), None).await;
+			server_static.broadcast_batched(&WorldUpdate::from(Sound::at(position, DropItem)), None).await;
 		});
 	}
 
@@ -175,11 +223,20 @@ impl Server {
 		}
 		drop(drops_guard);
 
-		self.broadcast(&WorldUpdate::from((zone, zone_drops_owned)), None).await;
+		self.broadcast_batched(&WorldUpdate::from((zone, zone_drops_owned)), None).await;
 
 		Some(removed_drop.item)
 	}
 
+	///index of `item` within the drops of `zone`, for use with [`Server::remove_drop`]
+	pub async fn find_drop(&self, zone: Point2<i32>, item: &Item) -> Option<usize> {
+		self.loot
+			.read().await
+			.get(&zone)?
+			.iter()
+			.position(|ground_item| ground_item.item == *item)
+	}
+
 	async fn remove_player(&self, player_to_remove: &Player) {
 		let mut players = self.players.write().await;
 		let index = players
@@ -188,56 +245,60 @@ impl Server {
 			.expect("this should be the only place where players get removed");
 		let player = players.swap_remove(index);
 		drop(players);
+		self.addons.anti_cheat.fingerprints.on_leave(&player).await;
+		self.addons.anti_cheat.name_rules.release(&player.character.read().await.name);
+		self.addons.matches.on_leave(self, &player).await;
+		self.addons.duels.on_leave(self, &player).await;
 		self.announce(format!("[-] {}", player.character.read().await.name)).await;
 		play_sound_for_everyone(self, MenuClose2, 2.0, 1.0).await;
 		pvp::team::change_to(self, player_to_remove, None).await;
+		self.spatial_index.write().await.remove(player_to_remove.id);
+		for remaining_player in self.players.read().await.iter() {
+			remaining_player.addon_data.write().await.replicated_creatures.remove(&player_to_remove.id);
+		}
 		self.remove_creature(&player_to_remove.id).await;
 	}
 
 	async fn remove_creature(&self, creature_id: &CreatureId) {
-		//this is a shortcut, as the creature technically still exists
-		//the proper way to remove a creature requires updating all remaining creatures which is expensive on bandwidth
-		self.broadcast(&CreatureUpdate {
-			id: *creature_id,
-			health: Some(0.0), //makes the creature intangible
-			affiliation: Some(Affiliation::Neutral), //ensures it doesnt show up on the map
-			..Default::default()
-		}, None).await;
+		self.broadcast_batched(&despawn(*creature_id), None).await;
 	}
 
-	async fn read_packets_forever(&self, source: &Player, mut reader: BufReader<OwnedReadHalf>) -> io::Result<()> {
+	///only returns once the connection is no longer usable
+	async fn read_packets_forever(&self, source: &Player, mut reader: BufReader<OwnedReadHalf>) -> io::Error {
+		//only fully received packets extend the deadline, so a connection can't be kept alive by trickling in partial packets
+		let mut deadline = Instant::now() + self.config.read_timeout;
+
 		loop {
 			let iteration = async {
 				//todo: copypasta
-				match reader.read_id().await? {
-					CreatureUpdate       ::ID => self.handle_packet(source, reader.read_packet::<CreatureUpdate       >().await?).await,
-					CreatureAction       ::ID => self.handle_packet(source, reader.read_packet::<CreatureAction       >().await?).await,
-					Hit                  ::ID => self.handle_packet(source, reader.read_packet::<Hit                  >().await?).await,
-					StatusEffect         ::ID => self.handle_packet(source, reader.read_packet::<StatusEffect         >().await?).await,
-					Projectile           ::ID => self.handle_packet(source, reader.read_packet::<Projectile           >().await?).await,
-					ChatMessageFromClient::ID => self.handle_packet(source, reader.read_packet::<ChatMessageFromClient>().await?).await,
-					AreaRequest::<Zone>  ::ID => self.handle_packet(source, reader.read_packet::<AreaRequest<Zone>    >().await?).await,
-					AreaRequest::<Region>::ID => self.handle_packet(source, reader.read_packet::<AreaRequest<Region>  >().await?).await,
-					unexpected_packet_id => panic!("unexpected packet id {unexpected_packet_id:?}")
+				match read_before(deadline, reader.read_id()).await? {
+					CreatureUpdate       ::ID => self.handle_packet(source, read_before(deadline, reader.read_packet::<CreatureUpdate       >()).await?).await,
+					CreatureAction       ::ID => self.handle_packet(source, read_before(deadline, reader.read_packet::<CreatureAction       >()).await?).await,
+					Hit                  ::ID => self.handle_packet(source, read_before(deadline, reader.read_packet::<Hit                  >()).await?).await,
+					StatusEffect         ::ID => self.handle_packet(source, read_before(deadline, reader.read_packet::<StatusEffect         >()).await?).await,
+					Projectile           ::ID => self.handle_packet(source, read_before(deadline, reader.read_packet::<Projectile           >()).await?).await,
+					ChatMessageFromClient::ID => self.handle_packet(source, read_before(deadline, reader.read_packet::<ChatMessageFromClient>()).await?).await,
+					AreaRequest::<Zone>  ::ID => self.handle_packet(source, read_before(deadline, reader.read_packet::<AreaRequest<Zone>    >()).await?).await,
+					AreaRequest::<Region>::ID => self.handle_packet(source, read_before(deadline, reader.read_packet::<AreaRequest<Region>  >()).await?).await,
+					unexpected_packet_id => return Err(io::Error::new(InvalidData, format!("unexpected packet id {unexpected_packet_id:?}")))
 				};
 
 				io::Result::<_>::Ok(()) //todo: why do we need explicit type annotation here?
 			};
 
-			select! {
-				biased;
-				result = iteration => { result?; continue; },
-				() = sleep(Duration::from_secs(10)) => { self.kick(source, "connection timeout").await; }
+			if let Err(error) = iteration.await {
+				return error;
 			}
+			deadline = Instant::now() + self.config.read_timeout;
 		}
 	}
 }
 
+///the creature itself is handled by the spatial index, as only players in range get to see it
 //todo: way too much pvp stuff in here
 //todo: status effects (including team hearts)
 async fn send_existing_creatures(server: &Server, player: &Player) {
 	pvp::team::display::reload(player, &[]).await;
-	let own_team = player.addon_data.read().await.team;
 	server
 		.players
 		.read()
@@ -245,28 +306,29 @@ async fn send_existing_creatures(server: &Server, player: &Player) {
 		.iter()
 		.filter(|existing_player| !ptr::eq(existing_player.as_ref(), player))
 		.map(|existing_player| async {
-			let character = existing_player
-				.character
-				.read()
-				.await;
-
-			let other_team = existing_player.addon_data.read().await.team;
-			let is_teammate = own_team.is_some() && own_team == other_team;
-
-			let creature_update = character
-				.to_update(existing_player.id)
-				.tap_mut(|packet| {
-					packet.affiliation = Some(if is_teammate { Affiliation::Player } else { Affiliation::Enemy });
-					packet.rarity = Some(if is_teammate { 0 } else { 4 });
-				});
-			let map_head = map_head::create(&character, existing_player.id);
-			drop(character);
-
-			player.send_ignoring(&creature_update).await;
+			let map_head = map_head::create(&*existing_player.character.read().await, existing_player.map_head_id);
 			player.send_ignoring(&map_head).await;
 		})
 		.pipe(join_all)
 		.await;
+	server.refresh_interest(player).await;
+}
+
+//this is a shortcut, as the creature technically still exists
+//the proper way to remove a creature requires updating all remaining creatures which is expensive on bandwidth
+pub fn despawn(creature_id: CreatureId) -> CreatureUpdate {
+	CreatureUpdate {
+		id: creature_id,
+		health: Some(0.0), //makes the creature intangible
+		affiliation: Some(Affiliation::Neutral), //ensures it doesnt show up on the map
+		..Default::default()
+	}
+}
+
+async fn read_before<T>(deadline: Instant, read: impl Future<Output = io::Result<T>>) -> io::Result<T> {
+	timeout_at(deadline, read)
+		.await
+		.map_err(|_| io::Error::new(TimedOut, "connection timeout"))?
 }
 
 fn split_and_buffer(stream: TcpStream) -> (BufReader<OwnedReadHalf>, BufWriter<OwnedWriteHalf>) {
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::io::ErrorKind::NotFound;
+use std::str::FromStr;
+use std::time::Duration;
+
+use tap::Tap;
+
+use protocol::utils::constants::SIZE_BLOCK;
+
+use crate::addon::anti_cheat::fingerprint;
+use crate::addon::anti_cheat::violation::{Check, Policy};
+
+///written to disk on first launch. keys missing from the file on disk fall back to these
+const DEFAULTS: &str = concat!(
+	"ticks_per_second;20\n",
+	"interest_radius;512\n",
+	"read_timeout;10\n",
+	"afk_warning;240\n",
+	"afk_kick;300\n",
+	"anti_cheat.movement;1;0:rubber_band,20:kick\n",
+	"anti_cheat.acceleration;1;0:log_only\n",
+	"anti_cheat.timewarp;2;0:log_only,4:notify_admins,10:kick\n",
+	"anti_cheat.combat;1;0:log_only,5:notify_admins,15:kick\n",
+	"anti_cheat.hit;1;0:log_only,10:notify_admins,25:kick,50:temp_ban\n",
+	"anti_cheat.projectile;1;0:log_only,10:notify_admins,25:kick\n",
+	"anti_cheat.projectile_kind;1;0:log_only\n",
+	"anti_cheat.status_effect;2;0:log_only,6:notify_admins,20:kick,40:temp_ban\n",
+	"anti_cheat.status_effect_skill;1;0:log_only\n",
+	"anti_cheat.inventory;5;0:notify_admins,15:kick,30:temp_ban\n",
+	"anti_cheat.character;10;0:kick,30:temp_ban\n",
+	"anti_cheat.skill_cap;1;0:log_only\n",
+	"anti_cheat.mirror_to_discord;false\n",
+	"anti_cheat.max_join_level;500\n",
+	"anti_cheat.trust_initial_inventory;true\n",
+	"anti_cheat.fingerprint_policy;warn_admins\n",
+	"rewards.base;0.25\n",
+	"rewards.min_level_factor;0.25\n",
+	"rewards.max_level_factor;2\n",
+	"rewards.farming_window;600\n",
+	"rewards.repeat_kill_factor;0.5"
+);
+
+///server settings, loaded from `config.csv` on startup
+#[derive(Debug)]
+pub struct Config {
+	pub ticks_per_second: u64,
+	///configured in blocks
+	pub interest_radius: i64,
+	///how long a connection may stay silent before it gets dropped
+	pub read_timeout: Duration,
+	///how long a player may idle before they get warned and kicked, respectively
+	pub afk_warning: Duration,
+	pub afk_kick: Duration,
+	///severity and escalation of each anti-cheat check
+	pub anti_cheat_policies: HashMap<Check, Policy>,
+	///whether every violation gets posted to the discord admin channel, not just the ones that require attention
+	pub mirror_violations_to_discord: bool,
+	///characters above this level get turned away, as there is no telling how they got there
+	pub max_join_level: i32,
+	///clients never tell the server what's in their bags, so items of unknown origin can either always or never be dropped
+	pub trust_initial_inventory: bool,
+	///what happens when a character comes back different from how it left
+	pub fingerprint_policy: fingerprint::Policy,
+	///killing an equally strong opponent is worth this much of the killer's current level
+	pub reward_base: f32,
+	///bounds for how much the strength difference between victim and killer affects the reward
+	pub reward_min_level_factor: f32,
+	pub reward_max_level_factor: f32,
+	///kills of the same victim within this time reduce the reward
+	pub reward_farming_window: Duration,
+	///each recent kill of the same victim multiplies the reward by this
+	pub reward_repeat_kill_factor: f32
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		let file_content = match fs::read_to_string(Self::FILE_PATH) {
+			Ok(content) => content,
+
+			Err(error) if error.kind() == NotFound => {
+				DEFAULTS
+					.tap(|content| fs::write(Self::FILE_PATH, content).unwrap())
+					.to_owned()
+			}
+
+			Err(error) => panic!("failed to load {} - {}", Self::FILE_PATH, error)
+		};
+
+		let values = DEFAULTS
+			.lines()
+			.chain(file_content.lines()) //later entries override earlier ones
+			.filter_map(|line| line.split_once(';'))
+			.collect::<HashMap<_, _>>();
+
+		Self {
+			ticks_per_second: parse(&values, "ticks_per_second"),
+			interest_radius: parse::<i64>(&values, "interest_radius") * SIZE_BLOCK,
+			read_timeout: seconds(&values, "read_timeout"),
+			afk_warning: seconds(&values, "afk_warning"),
+			afk_kick: seconds(&values, "afk_kick"),
+			anti_cheat_policies: Check::ALL
+				.into_iter()
+				.map(|check| (check, parse(&values, &format!("anti_cheat.{check}"))))
+				.collect(),
+			mirror_violations_to_discord: parse(&values, "anti_cheat.mirror_to_discord"),
+			max_join_level: parse(&values, "anti_cheat.max_join_level"),
+			trust_initial_inventory: parse(&values, "anti_cheat.trust_initial_inventory"),
+			fingerprint_policy: parse(&values, "anti_cheat.fingerprint_policy"),
+			reward_base: parse(&values, "rewards.base"),
+			reward_min_level_factor: parse(&values, "rewards.min_level_factor"),
+			reward_max_level_factor: parse(&values, "rewards.max_level_factor"),
+			reward_farming_window: seconds(&values, "rewards.farming_window"),
+			reward_repeat_kill_factor: parse(&values, "rewards.repeat_kill_factor")
+		}
+	}
+}
+
+impl Config {
+	const FILE_PATH: &'static str = "config.csv";
+}
+
+fn parse<T: FromStr>(values: &HashMap<&str, &str>, key: &str) -> T
+	where T::Err: Debug
+{
+	values[key]
+		.trim()
+		.parse()
+		.unwrap_or_else(|error| panic!("invalid value for {key} in {} - {error:?}", Config::FILE_PATH))
+}
+
+fn seconds(values: &HashMap<&str, &str>, key: &str) -> Duration {
+	Duration::from_secs_f32(parse(values, key))
+}
@@ -1,26 +1,123 @@
+use std::ops::Range;
+use std::sync::{Arc, Mutex, PoisonError};
+
 use protocol::packet::common::CreatureId;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+///the id space is partitioned into fixed ranges, so different kinds of creatures can never collide
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum IdRange {
+	Server,
+	HudDummies,
+	Players,
+	MapHeads,
+	Npcs
+}
+
+impl IdRange {
+	pub const fn bounds(self) -> Range<i64> {
+		match self {
+			Self::Server     =>    0..1,
+			Self::HudDummies =>    1..4,
+			Self::Players    =>    4..1024,
+			Self::MapHeads   => 1024..2048,
+			Self::Npcs       => 2048..4096
+		}
+	}
+
+	///for ranges whose ids are assigned statically rather than claimed
+	pub fn nth(self, index: usize) -> CreatureId {
+		let bounds = self.bounds();
+		let id = bounds.start + index as i64;
+		assert!(bounds.contains(&id), "{self:?} only has {} ids", bounds.end - bounds.start);
+		CreatureId(id)
+	}
+}
+
+const WORD_SIZE: i64 = u64::BITS as i64;
+const WORD_COUNT: usize = locate(IdRange::Npcs.bounds().end).0;
+
+#[derive(Debug, Clone, Default)]
 pub struct CreatureIdPool {
-	claimed_ids: Vec<i64>
+	claimed: Arc<Mutex<Bitset>>
 }
 
 impl CreatureIdPool {
-	pub fn claim(&mut self) -> CreatureId {
-		let mut x = 0_i64;
-		while self.claimed_ids.contains(&x) {
-			x += 1;
+	///returns `None` if the range is exhausted
+	pub fn claim(&self, range: IdRange) -> Option<ClaimedId> {
+		let id = self.claimed
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.claim_first_in(range.bounds())?;
+
+		Some(ClaimedId {
+			id,
+			pool: Arc::clone(&self.claimed)
+		})
+	}
+}
+
+///an id that stays claimed for as long as this guard lives
+#[derive(Debug)]
+pub struct ClaimedId {
+	id: CreatureId,
+	pool: Arc<Mutex<Bitset>>
+}
+
+impl ClaimedId {
+	pub const fn id(&self) -> CreatureId {
+		self.id
+	}
+}
+
+impl Drop for ClaimedId {
+	fn drop(&mut self) {
+		self.pool
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.free(self.id);
+	}
+}
+
+#[derive(Debug)]
+struct Bitset([u64; WORD_COUNT]);
+
+impl Default for Bitset {
+	fn default() -> Self {
+		Self([0; WORD_COUNT])
+	}
+}
+
+impl Bitset {
+	fn claim_first_in(&mut self, range: Range<i64>) -> Option<CreatureId> {
+		let mut id = range.start;
+		while id < range.end {
+			let (word_index, offset) = locate(id);
+			let free_bits = !self.0[word_index] >> offset;
+
+			if free_bits == 0 {
+				id += WORD_SIZE - i64::from(offset); //word is full, skip to the next one
+				continue;
+			}
+
+			let bit = offset + free_bits.trailing_zeros();
+			id += i64::from(free_bits.trailing_zeros());
+			if id >= range.end {
+				break;
+			}
+			self.0[word_index] |= 1 << bit;
+			return Some(CreatureId(id));
 		}
-		self.claimed_ids.push(x);
-		CreatureId(x)
+
+		None
 	}
 
-	pub fn free(&mut self, id: CreatureId) {
-		self.claimed_ids.swap_remove(
-			self.claimed_ids
-				.iter()
-				.position(|other| *other == id.0)
-				.expect("attempted to free a non-existing CreatureId")
-		);
+	const fn free(&mut self, id: CreatureId) {
+		let (word_index, offset) = locate(id.0);
+		self.0[word_index] &= !(1 << offset);
 	}
-}
\ No newline at end of file
+}
+
+#[expect(clippy::cast_sign_loss, reason = "ids are never negative")]
+const fn locate(id: i64) -> (usize, u32) {
+	((id / WORD_SIZE) as usize, (id % WORD_SIZE) as u32)
+}
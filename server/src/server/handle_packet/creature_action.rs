@@ -5,6 +5,8 @@ use protocol::packet::creature_action::Kind::*;
 use protocol::packet::world_update::{Pickup, sound, Sound};
 use protocol::utils::constants::SIZE_BLOCK;
 
+use crate::addon::anti_cheat;
+use crate::addon::anti_cheat::violation::{Check, Flag};
 use crate::server::handle_packet::HandlePacket;
 use crate::server::player::Player;
 use crate::server::Server;
@@ -16,6 +18,7 @@ impl HandlePacket<CreatureAction> for Server {
 				source.notify("bombs are disabled").await;
 
 				//the player consumed a bomb, so we need to reimburse it
+				source.addon_data.write().await.anti_cheat_data.receive_item(packet.item.clone());
 				let pickup = Pickup {
 					interactor: source.id,
 					item: packet.item
@@ -33,6 +36,11 @@ impl HandlePacket<CreatureAction> for Server {
 				let Some(item) = self.remove_drop(packet.zone, packet.item_index as usize).await
 					else { return; }; //todo: kick if invalid?
 
+				if self.addons.matches.on_pick_up(self, source, &item).await {
+					return; //flags stay out of inventories
+				}
+
+				source.addon_data.write().await.anti_cheat_data.receive_item(item.clone());
 				source.send_ignoring(&WorldUpdate {
 					pickups: vec![Pickup { item, interactor: source.id }],
 					sounds: vec![Sound::at(source.character.read().await.position, sound::Kind::Pickup)],
@@ -44,6 +52,12 @@ impl HandlePacket<CreatureAction> for Server {
 					self.kick(source, "void item dropped").await;
 					return;
 				}
+				if let Err(violation) = anti_cheat::inventory::inspect_drop(self, source, &packet.item).await.flag(Check::Inventory)
+					&& self.addons.anti_cheat.report(self, source, violation).await
+				{
+					return;
+				}
+
 				let character = source.character.read().await;
 				let position = character.position - Vector3::new(0, 0, SIZE_BLOCK);
 				let rotation = character.rotation.yaw;
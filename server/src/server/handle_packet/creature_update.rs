@@ -1,8 +1,13 @@
+use futures::future::join_all;
+use tap::Pipe;
+
 use protocol::packet::CreatureUpdate;
 
-use crate::addon::{anti_cheat, pvp};
+use crate::addon::{afk_detector, anti_cheat, pvp, spectator};
 use crate::addon::fix_cutoff_animations;
-use crate::addon::traffic_filter::filter;
+use crate::addon::pvp::kill_tracker;
+use crate::addon::traffic_filter::{compress, filter};
+use crate::server::creature::Creature;
 use crate::server::handle_packet::HandlePacket;
 use crate::server::player::Player;
 use crate::server::Server;
@@ -10,12 +15,14 @@ use crate::server::Server;
 impl HandlePacket<CreatureUpdate> for Server {
 	#[expect(clippy::significant_drop_tightening, reason = "false positive")]
 	async fn handle_packet(&self, source: &Player, mut packet: CreatureUpdate) {
-		if let Err(message) = anti_cheat::inspect_creature_update(source, &packet).await {
-			self.kick(source, message).await;
+		if let Err(violation) = anti_cheat::inspect_creature_update(source, &packet).await
+			&& self.addons.anti_cheat.report(self, source, violation).await
+		{
 			return;
 		}
 
 		self.addons.air_time_tracker.on_creature_update(source).await;
+		afk_detector::on_creature_update(source, &packet).await;
 		pvp::on_creature_update(self, source, &packet).await;
 
 		let mut character = source.character.write().await;
@@ -23,17 +30,48 @@ impl HandlePacket<CreatureUpdate> for Server {
 		character.update(&packet);
 		let character = character.downgrade();
 
-		if !filter(&mut packet, &snapshot, &character) {
+		let has_data_left = filter(&mut packet, &snapshot, &character);
+		let current_state = character.clone();
+		drop(character);
+
+		if snapshot.health > 0.0 && current_state.health <= 0.0 {
+			kill_tracker::on_death(self, source).await;
+		}
+		if snapshot.health <= 0.0 && current_state.health > 0.0 {
+			self.addons.matches.on_respawn(self, source).await;
+		}
+
+		if packet.position.is_some() {
+			self.update_interest(source).await;
+		}
+
+		if !has_data_left || spectator::is_spectating(source).await {
 			return;
 		}
-		drop(character);
 
 		fix_cutoff_animations(&mut packet, &snapshot);
 
-		if pvp::broadcast(self, source, &packet).await {
-			return;
-		};
+		self.players_in_range_of(source)
+			.await
+			.iter()
+			.map(|recipient| relay(source, recipient, &packet, &current_state))
+			.pipe(join_all)
+			.await;
+	}
+}
+
+async fn relay(source: &Player, recipient: &Player, packet: &CreatureUpdate, current_state: &Creature) {
+	let mut addon_data = recipient.addon_data.write().await;
+	let Some(last_sent) = addon_data.replicated_creatures.get_mut(&source.id)
+		else { return; }; //a full snapshot is on its way already
 
-		self.broadcast(&packet, Some(source)).await;
+	let mut packet = packet.clone();
+	if !compress(&mut packet, last_sent, current_state) {
+		return;
 	}
+	last_sent.update(&packet);
+	drop(addon_data);
+
+	pvp::adapt_for(recipient, source, &mut packet).await;
+	recipient.enqueue(&packet).await;
 }
\ No newline at end of file
@@ -7,7 +7,10 @@ use protocol::packet::hit::Kind::{*, Absorb, Block};
 use protocol::packet::world_update::{Sound, sound};
 use protocol::packet::world_update::sound::Kind::*;
 
-use crate::addon::balancing;
+use crate::addon::{anti_cheat, balancing, spectator};
+use crate::addon::anti_cheat::violation::{Check, Flag};
+use crate::addon::pvp;
+use crate::addon::pvp::kill_tracker;
 use crate::server::handle_packet::HandlePacket;
 use crate::server::player::Player;
 use crate::server::Server;
@@ -16,6 +19,18 @@ impl HandlePacket<Hit> for Server {
 	async fn handle_packet(&self, source: &Player, mut packet: Hit) {
 		let Some(target) = self.find_player_by_id(packet.target).await
 			else { return; };//can happen when the target disconnected in this moment
+		if let Err(violation) = anti_cheat::hit::inspect(source, &target, &packet).await.flag(Check::Hit)
+			&& self.addons.anti_cheat.report(self, source, violation).await
+		{
+			return;
+		}
+		if spectator::is_spectating(source).await || spectator::is_spectating(&target).await {
+			return;
+		}
+		if packet.damage > 0.0 && source.id != target.id && !pvp::is_hostile(source, &target).await {
+			return; //safe zones, duels and teams
+		}
+
 		let target_character_guard = target.character.read().await;
 		let source_character_guard = source.character.read().await;
 
@@ -29,8 +44,10 @@ impl HandlePacket<Hit> for Server {
 			..Default::default()
 		};
 
+		let cause = kill_tracker::weapon_of(&source_character_guard);
 		drop((source_character_guard, target_character_guard));
-		target.send_ignoring(world_update).await;
+		kill_tracker::record_attack(source, &target, world_update.hits[0].damage, cause).await;
+		target.enqueue(world_update).await;
 	}
 }
 
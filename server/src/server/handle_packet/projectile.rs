@@ -1,11 +1,27 @@
 use protocol::packet::{Projectile, WorldUpdate};
 
+use crate::addon::{anti_cheat, spectator};
+use crate::addon::anti_cheat::violation::{Check, Flag};
 use crate::server::handle_packet::HandlePacket;
 use crate::server::player::Player;
 use crate::server::Server;
 
 impl HandlePacket<Projectile> for Server {
 	async fn handle_packet(&self, source: &Player, packet: Projectile) {
-		self.broadcast(&WorldUpdate::from(packet), Some(source)).await;
+		if let Err(violation) = anti_cheat::projectile::inspect(source, &packet).flag(Check::Projectile)
+			&& self.addons.anti_cheat.report(self, source, violation).await
+		{
+			return;
+		}
+		if let Err(violation) = anti_cheat::projectile::inspect_kind(source, &packet).await.flag(Check::ProjectileKind)
+			&& self.addons.anti_cheat.report(self, source, violation).await
+		{
+			return;
+		}
+		if spectator::is_spectating(source).await {
+			return;
+		}
+
+		self.broadcast_in_range(&WorldUpdate::from(packet), source).await;
 	}
-}
\ No newline at end of file
+}
@@ -1,28 +1,43 @@
-use std::sync::Arc;
-use std::time::Duration;
-
-use tokio::time::sleep;
-
 use protocol::nalgebra::Vector3;
 use protocol::packet::{Hit, StatusEffect, WorldUpdate};
 use protocol::packet::hit::Kind::*;
 use protocol::packet::status_effect::Kind::*;
-use protocol::packet::world_update::Sound;
-use protocol::packet::world_update::sound::Kind::*;
 
-use crate::addon::balancing;
+use crate::addon::{anti_cheat, balancing, spectator};
+use crate::addon::anti_cheat::violation::{Check, Flag};
 use crate::server::handle_packet::HandlePacket;
 use crate::server::player::Player;
 use crate::server::Server;
 
 impl HandlePacket<StatusEffect> for Server {
 	async fn handle_packet(&self, source: &Player, packet: StatusEffect) {
+		if packet.kind == Affection || anti_cheat::status_effect::is_echo(source, &packet).await {
+			return; //echoed by the client when applied by the server, no need to re-broadcast
+		}
+
+		if let Err(violation) = anti_cheat::status_effect::inspect(source, &packet).await.flag(Check::StatusEffect)
+			&& self.addons.anti_cheat.report(self, source, violation).await
+		{
+			return;
+		}
+		if let Err(violation) = anti_cheat::status_effect::inspect_skill(source, &packet).await.flag(Check::StatusEffectSkill)
+			&& self.addons.anti_cheat.report(self, source, violation).await
+		{
+			return;
+		}
+		if spectator::is_spectating(source).await {
+			return;
+		}
+
 		match packet.kind {
 			Poison => {
 				let Some(target) = self.find_player_by_id(packet.target).await
 					else { return; };//can happen when the target disconnected in this moment
+				if spectator::is_spectating(&target).await {
+					return;
+				}
 
-				apply_poison(source, target, &packet).await;
+				apply_poison(self, source, &target, &packet).await;
 			}
 			WarFrenzy => {
 				balancing::buff_warfrenzy(&packet, self).await;
@@ -30,16 +45,15 @@ impl HandlePacket<StatusEffect> for Server {
 			ManaShield => {
 				source.notify(format!("manashield: {}", packet.modifier)).await;
 			}
-			Affection => return, //echoed by the client when applied by the server, no need to re-broadcast
 			_ => ()
 		}
 
 
-		self.broadcast(&WorldUpdate::from(packet), Some(source)).await;
+		self.broadcast_in_range(&WorldUpdate::from(packet), source).await;
 	}
 }
 
-async fn apply_poison(source: &Player, target: Arc<Player>, status_effect: &StatusEffect) {
+async fn apply_poison(server: &Server, source: &Player, target: &Player, status_effect: &StatusEffect) {
 	let source_character_guard = source.character.read().await;
 	let target_character_guard = target.character.read().await;
 
@@ -60,32 +74,5 @@ async fn apply_poison(source: &Player, target: Arc<Player>, status_effect: &Stat
 	drop(source_character_guard);
 	drop(target_character_guard);
 
-	let world_update = WorldUpdate {
-		sounds: vec![Sound::at(hit.position, SlimeGroan)],
-		hits: vec![hit],
-		..Default::default()
-	};
-
-	let tick_count = status_effect.duration / 500;
-
-	tokio::spawn(async move {
-		let mut nth = 0;
-		loop {
-			nth += 1;
-
-			if target.character.read().await.health == 0.0 {
-				break;
-			}
-
-			if target.send(&world_update).await.is_err() {
-				break; //disconnects are handled in the reading task
-			};
-
-			if nth == tick_count {
-				break;
-			}
-
-			sleep(Duration::from_millis(500)).await;
-		}
-	});
-}
\ No newline at end of file
+	server.addons.poison_tracker.apply(hit, status_effect.duration).await;
+}
@@ -9,13 +9,20 @@ use crate::server::Server;
 impl HandlePacket<AreaRequest<Zone>> for Server {
 	async fn handle_packet(&self, source: &Player, packet: AreaRequest<Zone>) {
 		let p48sub = P48sub([0_u8; 16]);
+		let loot = self.loot
+			.read()
+			.await
+			.iter()
+			.filter(|(zone, _)| (*zone - packet.0).amax() <= 1) //requested + adjacent zones
+			.map(|(zone, zone_loot)| (*zone, zone_loot.clone()))
+			.collect();
+
 		let world_update = WorldUpdate {
-			//todo: filter to just this + adjacent zones
-			loot: self.loot.read().await.clone(),
+			loot,
 			p48: [(packet.0, vec![p48sub])].into(),
 			..Default::default()
 		};
 
 		source.send_ignoring(&world_update).await;
 	}
-}
\ No newline at end of file
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::mem;
+
+use protocol::packet::{CreatureUpdate, WorldUpdate};
+use protocol::packet::common::CreatureId;
+
+///collects everything that is supposed to be sent to a player during the current tick,
+///so it can be flushed as few packets as possible at the end of it
+#[derive(Debug, Default)]
+pub struct Outbox {
+	creature_updates: HashMap<CreatureId, CreatureUpdate>,
+	world_update: WorldUpdate
+}
+
+pub trait Enqueue<Packet> {
+	fn enqueue(&mut self, packet: &Packet);
+}
+
+impl Enqueue<CreatureUpdate> for Outbox {
+	fn enqueue(&mut self, packet: &CreatureUpdate) {
+		self.creature_updates
+			.entry(packet.id)
+			.and_modify(|pending| pending.merge(packet))
+			.or_insert_with(|| packet.clone());
+	}
+}
+
+impl Enqueue<WorldUpdate> for Outbox {
+	fn enqueue(&mut self, packet: &WorldUpdate) {
+		let pending = &mut self.world_update;
+
+		pending.blocks        .extend_from_slice(&packet.blocks);
+		pending.hits          .extend_from_slice(&packet.hits);
+		pending.particles     .extend_from_slice(&packet.particles);
+		pending.sounds        .extend_from_slice(&packet.sounds);
+		pending.projectiles   .extend_from_slice(&packet.projectiles);
+		pending.world_objects .extend_from_slice(&packet.world_objects);
+		pending.loot          .extend(packet.loot.clone()); //each entry contains the entire zone, so newer ones replace older ones
+		pending.p48           .extend(packet.p48.clone());
+		pending.pickups       .extend_from_slice(&packet.pickups);
+		pending.kills         .extend_from_slice(&packet.kills);
+		pending.attacks       .extend_from_slice(&packet.attacks);
+		pending.status_effects.extend_from_slice(&packet.status_effects);
+		pending.missions      .extend_from_slice(&packet.missions);
+	}
+}
+
+impl Outbox {
+	///empties the outbox. the world update is `None` if nothing was queued for it
+	pub fn take(&mut self) -> (Vec<CreatureUpdate>, Option<WorldUpdate>) {
+		let creature_updates = mem::take(&mut self.creature_updates)
+			.into_values()
+			.collect();
+
+		let world_update = mem::take(&mut self.world_update);
+		let world_update = (world_update != WorldUpdate::default()).then_some(world_update);
+
+		(creature_updates, world_update)
+	}
+}
+
+trait Merge {
+	fn merge(&mut self, newer: &Self);
+}
+
+impl Merge for CreatureUpdate {
+	#[expect(clippy::cognitive_complexity, reason = "false positive")]
+	fn merge(&mut self, newer: &Self) {
+		//todo: macro
+		if newer.position         .is_some() { self.position          = newer.position }
+		if newer.rotation         .is_some() { self.rotation          = newer.rotation }
+		if newer.velocity         .is_some() { self.velocity          = newer.velocity }
+		if newer.acceleration     .is_some() { self.acceleration      = newer.acceleration }
+		if newer.velocity_extra   .is_some() { self.velocity_extra    = newer.velocity_extra }
+		if newer.head_tilt        .is_some() { self.head_tilt         = newer.head_tilt }
+		if newer.flags_physics    .is_some() { self.flags_physics     .clone_from(&newer.flags_physics) }
+		if newer.affiliation      .is_some() { self.affiliation       = newer.affiliation }
+		if newer.race             .is_some() { self.race              = newer.race }
+		if newer.animation        .is_some() { self.animation         = newer.animation }
+		if newer.animation_time   .is_some() { self.animation_time    = newer.animation_time }
+		if newer.combo            .is_some() { self.combo             = newer.combo }
+		if newer.combo_timeout    .is_some() { self.combo_timeout     = newer.combo_timeout }
+		if newer.appearance       .is_some() { self.appearance        .clone_from(&newer.appearance) }
+		if newer.flags            .is_some() { self.flags             .clone_from(&newer.flags) }
+		if newer.effect_time_dodge.is_some() { self.effect_time_dodge = newer.effect_time_dodge }
+		if newer.effect_time_stun .is_some() { self.effect_time_stun  = newer.effect_time_stun }
+		if newer.effect_time_fear .is_some() { self.effect_time_fear  = newer.effect_time_fear }
+		if newer.effect_time_chill.is_some() { self.effect_time_chill = newer.effect_time_chill }
+		if newer.effect_time_wind .is_some() { self.effect_time_wind  = newer.effect_time_wind }
+		if newer.show_patch_time  .is_some() { self.show_patch_time   = newer.show_patch_time }
+		if newer.occupation       .is_some() { self.occupation        = newer.occupation }
+		if newer.specialization   .is_some() { self.specialization    = newer.specialization }
+		if newer.mana_charge      .is_some() { self.mana_charge       = newer.mana_charge }
+		if newer.unknown24        .is_some() { self.unknown24         = newer.unknown24 }
+		if newer.unknown25        .is_some() { self.unknown25         = newer.unknown25 }
+		if newer.aim_offset       .is_some() { self.aim_offset        = newer.aim_offset }
+		if newer.health           .is_some() { self.health            = newer.health }
+		if newer.mana             .is_some() { self.mana              = newer.mana }
+		if newer.blocking_gauge   .is_some() { self.blocking_gauge    = newer.blocking_gauge }
+		if newer.multipliers      .is_some() { self.multipliers       .clone_from(&newer.multipliers) }
+		if newer.unknown31        .is_some() { self.unknown31         = newer.unknown31 }
+		if newer.unknown32        .is_some() { self.unknown32         = newer.unknown32 }
+		if newer.level            .is_some() { self.level             = newer.level }
+		if newer.experience       .is_some() { self.experience        = newer.experience }
+		if newer.master           .is_some() { self.master            = newer.master }
+		if newer.unknown36        .is_some() { self.unknown36         = newer.unknown36 }
+		if newer.rarity           .is_some() { self.rarity            = newer.rarity }
+		if newer.unknown38        .is_some() { self.unknown38         = newer.unknown38 }
+		if newer.home_zone        .is_some() { self.home_zone         = newer.home_zone }
+		if newer.home             .is_some() { self.home              = newer.home }
+		if newer.zone_to_reveal   .is_some() { self.zone_to_reveal    = newer.zone_to_reveal }
+		if newer.unknown42        .is_some() { self.unknown42         = newer.unknown42 }
+		if newer.consumable       .is_some() { self.consumable        .clone_from(&newer.consumable) }
+		if newer.equipment        .is_some() { self.equipment         .clone_from(&newer.equipment) }
+		if newer.name             .is_some() { self.name              .clone_from(&newer.name) }
+		if newer.skill_tree       .is_some() { self.skill_tree        .clone_from(&newer.skill_tree) }
+		if newer.mana_cubes       .is_some() { self.mana_cubes        = newer.mana_cubes }
+	}
+}
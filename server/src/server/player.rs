@@ -6,36 +6,46 @@ use std::sync::atomic::AtomicBool;
 use tokio::io;
 use tokio::io::BufWriter;
 use tokio::net::tcp::OwnedWriteHalf;
-use tokio::sync::{oneshot, RwLock};
+use tokio::sync::{oneshot, Notify, RwLock};
 
-use protocol::packet::{ChatMessageFromServer, FromServer};
+use protocol::packet::{ChatMessageFromServer, FromServer, ServerTick};
 use protocol::packet::common::CreatureId;
 use protocol::utils::io_extensions::WritePacket;
 use protocol::WriteCwData;
 
 use crate::server::creature::Creature;
+use crate::server::creature_id_pool::ClaimedId;
+use crate::server::outbox::{Enqueue, Outbox};
 use crate::server::player::addon_data::AddonData;
 
 #[derive(Debug)]
 pub struct Player {
 	pub address: SocketAddr,
 	pub id: CreatureId,
+	pub map_head_id: CreatureId,
+	_claimed_ids: [ClaimedId; 2], //freed once the last reference to this player is gone
 	pub character: RwLock<Creature>,
 	writer: RwLock<BufWriter<OwnedWriteHalf>>,
+	outbox: RwLock<Outbox>,
+	flushed: Notify,
 	pub admin: AtomicBool, //todo: move to AddonData
 	pub kick_sender: RwLock<Option<oneshot::Sender<()>>>,
 	pub addon_data: RwLock<AddonData>
 }
 
 impl Player {
-	pub fn new(address: SocketAddr, id: CreatureId, creature: Creature, writer: BufWriter<OwnedWriteHalf>) -> (Self, oneshot::Receiver<()>) {
+	pub fn new(address: SocketAddr, id: ClaimedId, map_head_id: ClaimedId, creature: Creature, writer: BufWriter<OwnedWriteHalf>) -> (Self, oneshot::Receiver<()>) {
 		let (kick_sender, kick_receiver) = oneshot::channel();
 
 		let instance = Self {
 			address,
-			id,
+			id: id.id(),
+			map_head_id: map_head_id.id(),
+			_claimed_ids: [id, map_head_id],
 			character: RwLock::new(creature),
 			writer: RwLock::new(writer),
+			outbox: RwLock::default(),
+			flushed: Notify::new(),
 			admin: AtomicBool::default(),
 			kick_sender: RwLock::new(Some(kick_sender)),
 			addon_data: RwLock::default()
@@ -60,6 +70,32 @@ impl Player {
 		let _ = self.send(packet).await;
 	}
 
+	///queues a packet to be sent with the next tick, merged with everything else that got queued until then
+	pub async fn enqueue<Packet>(&self, packet: &Packet)
+		where Outbox: Enqueue<Packet>
+	{
+		self.outbox.write().await.enqueue(packet);
+	}
+
+	///sends everything that got queued since the last tick, followed by a [`ServerTick`]
+	pub async fn flush(&self) {
+		let (creature_updates, world_update) = self.outbox.write().await.take();
+
+		for creature_update in &creature_updates {
+			self.send_ignoring(creature_update).await;
+		}
+		if let Some(world_update) = world_update {
+			self.send_ignoring(&world_update).await;
+		}
+		self.send_ignoring(&ServerTick).await;
+		self.flushed.notify_waiters();
+	}
+
+	///resolves after the next flush. never resolves once the player has been removed
+	pub async fn flushed(&self) {
+		self.flushed.notified().await;
+	}
+
 	pub async fn notify(&self, message: impl Into<String>) {
 		self.send_ignoring(&ChatMessageFromServer {
 			source: CreatureId(0),
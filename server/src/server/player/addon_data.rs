@@ -1,7 +1,26 @@
+use std::collections::HashMap;
+
+use protocol::packet::common::CreatureId;
+
+use crate::addon::{afk_detector, spectator};
+use crate::addon::pvp::{duels, kill_tracker};
+use crate::addon::pvp::matches::arena;
 use crate::addon::anti_cheat::PlayerData;
+use crate::server::creature::Creature;
 
 #[derive(Debug, Default)]
 pub struct AddonData {
 	pub team: Option<i32>,
-	pub anti_cheat_data: PlayerData
+	pub duel: Option<duels::Engagement>,
+	pub spectating: Option<spectator::Spectating>,
+	///name of the safe zone this player is in
+	pub safe_zone: Option<String>,
+	///sitting out the rest of the current round of an elimination match
+	pub eliminated: bool,
+	pub anti_cheat_data: PlayerData,
+	pub afk_data: afk_detector::PlayerData,
+	pub kill_data: kill_tracker::PlayerData,
+	pub arena_data: arena::PlayerData,
+	///the state of each creature in range, as it was last sent to this player
+	pub replicated_creatures: HashMap<CreatureId, Creature>
 }
\ No newline at end of file
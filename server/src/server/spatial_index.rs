@@ -0,0 +1,204 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use futures::future::join_all;
+use tap::Pipe;
+
+use protocol::nalgebra::{Point2, Point3, Vector2};
+use protocol::packet::common::CreatureId;
+use protocol::utils::constants::SIZE_ZONE;
+
+use crate::addon::pvp;
+use crate::server::outbox::{Enqueue, Outbox};
+use crate::server::player::Player;
+use crate::server::{despawn, Server};
+
+pub fn zone_of(position: Point3<i64>) -> Point2<i32> {
+	position.xy().map(|scalar| (scalar / SIZE_ZONE) as i32)
+}
+
+///keeps track of which players are close enough to each other to exchange creature updates
+#[derive(Debug)]
+pub struct SpatialIndex {
+	///players further apart than this (horizontally) don't get to see each other
+	radius: i64,
+	zones: HashMap<Point2<i32>, HashSet<CreatureId>>,
+	positions: HashMap<CreatureId, Point3<i64>>,
+	in_range: HashMap<CreatureId, HashSet<CreatureId>> //always symmetric
+}
+
+#[derive(Debug)]
+pub struct RangeChanges {
+	pub entered: Vec<CreatureId>,
+	pub left: Vec<CreatureId>
+}
+
+impl SpatialIndex {
+	pub fn new(radius: i64) -> Self {
+		Self {
+			radius,
+			zones: HashMap::new(),
+			positions: HashMap::new(),
+			in_range: HashMap::new()
+		}
+	}
+
+	pub fn contains(&self, id: CreatureId) -> bool {
+		self.positions.contains_key(&id)
+	}
+
+	///inserts or moves `id`, returning who came into or went out of range as a result
+	pub fn update(&mut self, id: CreatureId, position: Point3<i64>) -> RangeChanges {
+		if let Some(previous_position) = self.positions.insert(id, position) {
+			self.remove_from_zone(id, zone_of(previous_position));
+		}
+		self.zones.entry(zone_of(position)).or_default().insert(id);
+
+		let now_in_range = self.find_in_range(id, position);
+		let previously_in_range = self.in_range.remove(&id).unwrap_or_default();
+
+		let changes = RangeChanges {
+			entered: now_in_range.difference(&previously_in_range).copied().collect(),
+			left: previously_in_range.difference(&now_in_range).copied().collect()
+		};
+
+		for other in &changes.entered {
+			self.in_range.entry(*other).or_default().insert(id);
+		}
+		for other in &changes.left {
+			if let Some(others_in_range) = self.in_range.get_mut(other) {
+				others_in_range.remove(&id);
+			}
+		}
+		self.in_range.insert(id, now_in_range);
+
+		changes
+	}
+
+	pub fn remove(&mut self, id: CreatureId) {
+		if let Some(position) = self.positions.remove(&id) {
+			self.remove_from_zone(id, zone_of(position));
+		}
+		self.forget_neighbours(id);
+	}
+
+	///clears the in-range set of `id` without notifying anyone, so the next update treats everyone nearby as newly entered
+	pub fn forget_neighbours(&mut self, id: CreatureId) {
+		for other in self.in_range.remove(&id).unwrap_or_default() {
+			if let Some(others_in_range) = self.in_range.get_mut(&other) {
+				others_in_range.remove(&id);
+			}
+		}
+	}
+
+	pub fn in_range_of(&self, id: CreatureId) -> HashSet<CreatureId> {
+		self.in_range.get(&id).cloned().unwrap_or_default()
+	}
+
+	fn find_in_range(&self, id: CreatureId, position: Point3<i64>) -> HashSet<CreatureId> {
+		let center = zone_of(position);
+		let radius_in_zones = ((self.radius + SIZE_ZONE - 1) / SIZE_ZONE) as i32;
+
+		(-radius_in_zones..=radius_in_zones)
+			.flat_map(|dx| (-radius_in_zones..=radius_in_zones).map(move |dy| center + Vector2::new(dx, dy)))
+			.filter_map(|zone| self.zones.get(&zone))
+			.flatten()
+			.filter(|other| **other != id)
+			.filter(|other| {
+				let offset = (self.positions[other] - position).xy().cast::<f64>();
+				offset.norm() <= self.radius as f64
+			})
+			.copied()
+			.collect()
+	}
+
+	fn remove_from_zone(&mut self, id: CreatureId, zone: Point2<i32>) {
+		let Some(zone_members) = self.zones.get_mut(&zone) else { return; };
+		zone_members.remove(&id);
+		if zone_members.is_empty() {
+			self.zones.remove(&zone);
+		}
+	}
+}
+
+impl Server {
+	///re-evaluates who is in range of `player` after they moved.
+	///players coming into range get a full snapshot of each other, players going out of range get despawned for each other
+	pub async fn update_interest(&self, player: &Player) {
+		let position = player.character.read().await.position;
+
+		let mut spatial_index = self.spatial_index.write().await;
+		if !spatial_index.contains(player.id) {
+			return; //not spawned in yet
+		}
+		let changes = spatial_index.update(player.id, position);
+		drop(spatial_index);
+
+		self.apply_range_changes(player, changes).await;
+	}
+
+	///spawns `player` into the world, or respawns them after they lost track of their surroundings (e.g. after a teleport)
+	pub async fn refresh_interest(&self, player: &Player) {
+		let position = player.character.read().await.position;
+
+		let mut spatial_index = self.spatial_index.write().await;
+		spatial_index.forget_neighbours(player.id);
+		let changes = spatial_index.update(player.id, position);
+		drop(spatial_index);
+
+		self.apply_range_changes(player, changes).await;
+	}
+
+	pub async fn players_in_range_of(&self, player: &Player) -> Vec<Arc<Player>> {
+		let ids = self.spatial_index.read().await.in_range_of(player.id);
+
+		self.players
+			.read()
+			.await
+			.iter()
+			.filter(|other| ids.contains(&other.id))
+			.map(Arc::clone)
+			.collect()
+	}
+
+	///like [`Server::broadcast_batched`], but only reaches players in range of `source`
+	pub async fn broadcast_in_range<Packet>(&self, packet: &Packet, source: &Player)
+		where Outbox: Enqueue<Packet>
+	{
+		self.players_in_range_of(source)
+			.await
+			.iter()
+			.map(|player| player.enqueue(packet))
+			.pipe(join_all)
+			.await;
+	}
+
+	async fn apply_range_changes(&self, player: &Player, changes: RangeChanges) {
+		for id in changes.entered {
+			let Some(other) = self.find_player_by_id(id).await else { continue; }; //disconnecting
+
+			send_snapshot(player, &other).await;
+			send_snapshot(&other, player).await;
+		}
+
+		for id in changes.left {
+			send_despawn(player, id).await;
+
+			if let Some(other) = self.find_player_by_id(id).await {
+				send_despawn(&other, player.id).await;
+			}
+		}
+	}
+}
+
+async fn send_snapshot(recipient: &Player, subject: &Player) {
+	recipient.enqueue(&pvp::snapshot(recipient, subject).await).await;
+
+	let state = subject.character.read().await.clone();
+	recipient.addon_data.write().await.replicated_creatures.insert(subject.id, state);
+}
+
+async fn send_despawn(recipient: &Player, subject_id: CreatureId) {
+	recipient.enqueue(&despawn(subject_id)).await;
+	recipient.addon_data.write().await.replicated_creatures.remove(&subject_id);
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use colour::red_ln;
+use futures::future::join_all;
+use tap::Pipe;
+use tokio::time::{interval, MissedTickBehavior};
+
+use crate::server::Server;
+
+impl Server {
+	pub fn start_ticking(&self) {
+		let server_static = self.extend_lifetime();
+
+		tokio::spawn(async move {
+			let mut interval = interval(Duration::from_millis(1000 / server_static.config.ticks_per_second));
+			interval.set_missed_tick_behavior(MissedTickBehavior::Skip); //catching up would just produce empty ticks
+
+			loop {
+				interval.tick().await;
+				//a panicking tick must not end the loop, or nothing would ever get flushed again
+				if let Err(error) = tokio::spawn(server_static.tick()).await {
+					red_ln!("tick failed - {}", error);
+				}
+			}
+		});
+	}
+
+	async fn tick(&'static self) {
+		//addons may take their time (locks, disk), which mustn't hold back the flush
+		tokio::spawn(self.addons.on_tick(self));
+
+		self.players
+			.read()
+			.await
+			.iter()
+			.map(|player| player.flush())
+			.pipe(join_all)
+			.await;
+	}
+}
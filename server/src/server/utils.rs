@@ -1,23 +1,29 @@
+use std::mem;
 use std::mem::transmute;
 use std::sync::Arc;
 use std::time::Duration;
 
 use colour::white_ln;
-use tokio::time::sleep;
+use tokio::time::{sleep, timeout};
 
 use protocol::nalgebra::Point3;
-use protocol::packet::{ChatMessageFromServer, CreatureUpdate, ServerTick, WorldUpdate};
+use protocol::packet::{ChatMessageFromServer, CreatureUpdate, Hit, WorldUpdate};
 use protocol::packet::common::CreatureId;
 use protocol::packet::creature_update::Affiliation;
 use protocol::packet::creature_update::Affiliation::Pet;
 use protocol::packet::creature_update::Animation::Riding;
+use protocol::packet::hit::Kind::Normal;
 use protocol::packet::world_update::Kill;
 
+use crate::server::creature_id_pool::IdRange;
 use crate::server::player::Player;
 use crate::server::Server;
 
 use super::send_existing_creatures;
 
+///how long to wait for a tick before assuming the player is gone
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
 impl Server {
 	pub async fn announce(&self, text: impl Into<String>) {
 		let text = text.into();//todo: is there a way to prevent this boilerplate?
@@ -45,6 +51,8 @@ impl Server {
 	}
 
 	pub async fn teleport(&self, player: &Player, destination: Point3<i64>) {
+		player.addon_data.write().await.anti_cheat_data.authorize_teleport(destination);
+
 		let server_creature = CreatureUpdate {
 			id: CreatureId(0),
 			position: Some(destination),
@@ -52,10 +60,13 @@ impl Server {
 			animation: Some(Riding),
 			..Default::default()
 		};
-		player.send_ignoring(&server_creature).await;
-		sleep(Duration::from_millis(100)).await;
-		player.send_ignoring(&ServerTick).await;
-		player.send_ignoring(&ServerTick).await;
+		player.enqueue(&server_creature).await;
+		//the client only moves onto the server creature after it existed for a couple of ticks
+		for _ in 0..3 {
+			if timeout(FLUSH_TIMEOUT, player.flushed()).await.is_err() {
+				return; //disconnected
+			}
+		}
 		send_existing_creatures(self, player).await;
 	}
 
@@ -76,13 +87,17 @@ impl Server {
 	}
 }
 
-pub async fn give_xp(player: &Player, experience: i32) {
+pub async fn give_xp(server: &Server, player: &Player, experience: i32) {
+	let Some(dummy_id) = server.id_pool.claim(IdRange::Npcs)
+		else { return; };
+
 	let dummy = CreatureUpdate {
-		id: CreatureId(9999),
+		id: dummy_id.id(),
 		affiliation: Some(Affiliation::Enemy),
 		..Default::default()
 	};
-	player.send_ignoring(&dummy).await;
+	player.addon_data.write().await.anti_cheat_data.grant_experience(experience);
+	player.enqueue(&dummy).await;
 
 	let kill = Kill {
 		killer: player.id,
@@ -91,5 +106,57 @@ pub async fn give_xp(player: &Player, experience: i32) {
 		experience
 	};
 
-	player.send_ignoring(&WorldUpdate::from(kill)).await;
-}
\ No newline at end of file
+	player.enqueue(&WorldUpdate::from(kill)).await; //queued after the dummy, so it gets flushed after it as well
+
+	tokio::spawn(async move {
+		sleep(Duration::from_secs(1)).await; //make sure the kill got flushed before the id can be reused
+		drop(dummy_id);
+	});
+}
+
+///makes `field` safe to be joined with other fields by `;`, reversed by [`split_fields`]
+pub fn escape_field(field: &str) -> String {
+	let mut escaped = String::with_capacity(field.len());
+	for character in field.chars() {
+		match character {
+			'\\' => escaped.push_str("\\\\"),
+			';'  => escaped.push_str("\\;"),
+			'\n' => escaped.push_str("\\n"),
+			_    => escaped.push(character)
+		}
+	}
+	escaped
+}
+
+///splits a line of `;` separated fields, undoing [`escape_field`] on each of them
+pub fn split_fields(line: &str) -> Vec<String> {
+	let mut fields = vec![];
+	let mut current = String::new();
+	let mut characters = line.chars();
+	while let Some(character) = characters.next() {
+		match character {
+			';'  => fields.push(mem::take(&mut current)),
+			'\\' => match characters.next() {
+				Some('n')     => current.push('\n'),
+				Some(escaped) => current.push(escaped),
+				None          => ()
+			},
+			_    => current.push(character)
+		}
+	}
+	fields.push(current);
+	fields
+}
+
+pub async fn heal(player: &Player, amount: f32) {
+	let heal = Hit {
+		attacker: player.id,
+		target: player.id,
+		damage: -amount,
+		position: player.character.read().await.position,
+		kind: Normal,
+		..Default::default()
+	};
+
+	player.send_ignoring(&WorldUpdate::from(heal)).await;
+}
@@ -0,0 +1,9 @@
+mod spatial_index;
+mod creature_id_pool;
+mod violation_policy;
+mod naming;
+mod ratings;
+mod tournaments;
+mod safe_zones;
+mod fields;
+mod inventory;
@@ -0,0 +1,91 @@
+use std::iter;
+
+use protocol::packet::common::CreatureId;
+
+use crate::server::creature_id_pool::{CreatureIdPool, IdRange};
+
+const ALL_RANGES: [IdRange; 5] = [
+	IdRange::Server,
+	IdRange::HudDummies,
+	IdRange::Players,
+	IdRange::MapHeads,
+	IdRange::Npcs
+];
+
+#[test]
+fn ranges_are_contiguous_and_disjoint() {
+	for pair in ALL_RANGES.windows(2) {
+		let [lower, upper] = pair else { unreachable!() };
+		assert_eq!(lower.bounds().end, upper.bounds().start);
+	}
+	assert_eq!(IdRange::Server.bounds().start, 0);
+}
+
+#[test]
+fn claims_come_from_the_requested_range() {
+	let pool = CreatureIdPool::default();
+
+	for range in ALL_RANGES {
+		let claimed = pool.claim(range).unwrap();
+		assert_eq!(claimed.id(), CreatureId(range.bounds().start));
+	}
+}
+
+#[test]
+fn claimed_ids_are_unique() {
+	let pool = CreatureIdPool::default();
+
+	let first = pool.claim(IdRange::Players).unwrap();
+	let second = pool.claim(IdRange::Players).unwrap();
+	assert_ne!(first.id(), second.id());
+}
+
+#[test]
+fn exhausted_ranges_return_none() {
+	let pool = CreatureIdPool::default();
+	let bounds = IdRange::Players.bounds();
+
+	let claimed = iter::repeat_with(|| pool.claim(IdRange::Players).unwrap())
+		.take(bounds.clone().count())
+		.collect::<Vec<_>>();
+
+	assert_eq!(claimed.last().unwrap().id(), CreatureId(bounds.end - 1));
+	assert!(pool.claim(IdRange::Players).is_none());
+	assert!(pool.claim(IdRange::MapHeads).is_some()); //neighbouring ranges are unaffected
+}
+
+#[test]
+fn dropped_ids_can_be_claimed_again() {
+	let pool = CreatureIdPool::default();
+
+	let first = pool.claim(IdRange::Npcs).unwrap();
+	let second = pool.claim(IdRange::Npcs).unwrap();
+	let first_id = first.id();
+	drop(first);
+
+	assert_eq!(pool.claim(IdRange::Npcs).unwrap().id(), first_id);
+	assert_ne!(second.id(), first_id);
+}
+
+#[test]
+fn claims_skip_over_full_words() {
+	let pool = CreatureIdPool::default();
+
+	let _claimed = iter::repeat_with(|| pool.claim(IdRange::Npcs).unwrap())
+		.take(100)
+		.collect::<Vec<_>>();
+
+	assert_eq!(pool.claim(IdRange::Npcs).unwrap().id(), CreatureId(IdRange::Npcs.bounds().start + 100));
+}
+
+#[test]
+fn nth_maps_into_the_range() {
+	assert_eq!(IdRange::HudDummies.nth(0), CreatureId(1));
+	assert_eq!(IdRange::HudDummies.nth(2), CreatureId(3));
+}
+
+#[test]
+#[should_panic = "HudDummies only has 3 ids"]
+fn nth_rejects_indices_outside_the_range() {
+	IdRange::HudDummies.nth(3);
+}
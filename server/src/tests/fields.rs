@@ -0,0 +1,21 @@
+use crate::server::utils::{escape_field, split_fields};
+
+#[test]
+fn plain_fields_are_left_alone() {
+	assert_eq!(escape_field("Server.Bot"), "Server.Bot");
+	assert_eq!(split_fields("a;b;c"), ["a", "b", "c"]);
+}
+
+#[test]
+fn split_reverses_escape() {
+	let fields = ["a;b", "back\\slash", "line\nbreak", "", ";"];
+	let line = fields.map(escape_field).join(";");
+
+	assert_eq!(split_fields(&line), fields);
+}
+
+#[test]
+fn escaped_separators_dont_split() {
+	assert_eq!(split_fields("name[3];';';x"), ["name[3]", "'", "'", "x"]);
+	assert_eq!(split_fields(r"name[3];'\;';x"), ["name[3]", "';'", "x"]);
+}
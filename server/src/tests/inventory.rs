@@ -0,0 +1,52 @@
+use protocol::packet::common::item::Kind::Weapon;
+use protocol::packet::common::item::kind::Weapon::Sword;
+use protocol::packet::common::Item;
+
+use crate::addon::anti_cheat::inventory::Ledger;
+
+fn sword(seed: i32) -> Item {
+	Item {
+		kind: Weapon(Sword),
+		seed,
+		level: 1,
+		..Default::default()
+	}
+}
+
+#[test]
+fn received_items_can_be_dropped_once_each() {
+	let mut ledger = Ledger::default();
+	ledger.receive(sword(1));
+	ledger.receive(sword(1));
+
+	assert!(ledger.release(&sword(1), false));
+	assert!(ledger.release(&sword(1), false));
+	assert!(!ledger.release(&sword(1), false));
+}
+
+#[test]
+fn unknown_items_are_rejected_without_trust() {
+	let mut ledger = Ledger::default();
+	ledger.receive(sword(1));
+
+	assert!(!ledger.release(&sword(2), false));
+}
+
+#[test]
+fn unknown_items_are_trusted_only_once() {
+	let mut ledger = Ledger::default();
+
+	assert!(ledger.release(&sword(1), true));
+	assert!(!ledger.release(&sword(1), true));
+	assert!(ledger.release(&sword(2), true));
+}
+
+#[test]
+fn trusted_items_picked_up_again_are_accounted_for() {
+	let mut ledger = Ledger::default();
+
+	assert!(ledger.release(&sword(1), true));
+	ledger.receive(sword(1));
+	assert!(ledger.release(&sword(1), true));
+	assert!(!ledger.release(&sword(1), true));
+}
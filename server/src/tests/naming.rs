@@ -0,0 +1,27 @@
+use crate::addon::anti_cheat::naming::{is_reserved, skeleton_of};
+
+#[test]
+fn lookalikes_share_a_skeleton() {
+	assert_eq!(skeleton_of("Admin"), "admln");
+	assert_eq!(skeleton_of("4dm1n"), "admln");
+	assert_eq!(skeleton_of("@DM|N"), "admln");
+	assert_eq!(skeleton_of("$3rv3r"), "server");
+	assert_eq!(skeleton_of("B0T7"), "bott");
+}
+
+#[test]
+fn leaves_other_characters_alone() {
+	assert_eq!(skeleton_of("xy_z-9"), "xy_z-9");
+}
+
+#[test]
+fn reserves_whole_words_only() {
+	let reserved = skeleton_of("server");
+
+	assert!(is_reserved(&skeleton_of("Server"), &reserved));
+	assert!(is_reserved(&skeleton_of("S3RV3R"), &reserved));
+	assert!(is_reserved(&skeleton_of("Server_Bot"), &reserved));
+	assert!(is_reserved(&skeleton_of("the.server"), &reserved));
+	assert!(!is_reserved(&skeleton_of("Observer"), &reserved));
+	assert!(!is_reserved(&skeleton_of("Servers"), &reserved));
+}
@@ -0,0 +1,92 @@
+use protocol::utils::constants::combat_classes::BERSERKER;
+
+use crate::addon::pvp::ratings::{expected_score, rate, Placement, Rating};
+
+const ESTABLISHED: Rating = Rating { value: 1500.0, games: 100 };
+
+fn placement(team: Option<i32>, score: i32) -> Placement {
+	Placement {
+		name: String::new(),
+		class: BERSERKER,
+		team,
+		score
+	}
+}
+
+fn values_after(before: &[Rating], placements: &[Placement]) -> Vec<f32> {
+	rate(before, &placements.iter().collect::<Vec<_>>())
+		.into_iter()
+		.map(|rating| rating.unwrap().value)
+		.collect()
+}
+
+fn assert_close(actual: f32, expected: f32) {
+	assert!((actual - expected).abs() < 0.01, "{actual} != {expected}");
+}
+
+#[test]
+fn expected_scores_are_complementary() {
+	assert_close(expected_score(1500.0, 1500.0), 0.5);
+	assert_close(expected_score(1900.0, 1500.0), 0.909);
+	assert_close(expected_score(1700.0, 1300.0) + expected_score(1300.0, 1700.0), 1.0);
+}
+
+#[test]
+fn equal_players_trade_half_the_k_factor() {
+	let after = values_after(&[ESTABLISHED; 2], &[placement(None, 1), placement(None, 0)]);
+
+	assert_close(after[0], 1516.0);
+	assert_close(after[1], 1484.0);
+}
+
+#[test]
+fn draws_between_equals_change_nothing() {
+	let after = values_after(&[ESTABLISHED; 2], &[placement(None, 3), placement(None, 3)]);
+
+	assert_close(after[0], 1500.0);
+	assert_close(after[1], 1500.0);
+}
+
+#[test]
+fn upsets_are_worth_more() {
+	let favorite = Rating { value: 1900.0, ..ESTABLISHED };
+	let expected_win = values_after(&[favorite, ESTABLISHED], &[placement(None, 1), placement(None, 0)]);
+	let upset = values_after(&[favorite, ESTABLISHED], &[placement(None, 0), placement(None, 1)]);
+
+	assert!(upset[1] - 1500.0 > expected_win[0] - 1900.0);
+	assert_close(expected_win[0] + expected_win[1], 3400.0); //zero-sum with equal k-factors
+	assert_close(upset[0] + upset[1], 3400.0);
+}
+
+#[test]
+fn provisional_ratings_move_faster() {
+	let newcomer = Rating::default();
+	let after = values_after(&[newcomer, ESTABLISHED], &[placement(None, 1), placement(None, 0)]);
+
+	assert_close(after[0], 1532.0);
+	assert_close(after[1], 1484.0);
+}
+
+#[test]
+fn games_are_counted() {
+	let after = rate(&[ESTABLISHED; 2], &[&placement(None, 1), &placement(None, 0)]);
+
+	assert_eq!(after[0].unwrap().games, ESTABLISHED.games + 1);
+}
+
+#[test]
+fn teammates_are_not_rated_against_each_other() {
+	let placements = [placement(Some(1), 5), placement(Some(1), 5), placement(Some(2), 2)];
+	let after = values_after(&[ESTABLISHED; 3], &placements);
+
+	assert_close(after[0], 1516.0);
+	assert_close(after[1], 1516.0);
+	assert_close(after[2], 1484.0); //averaged over both opponents
+}
+
+#[test]
+fn players_without_opponents_stay_unrated() {
+	let after = rate(&[ESTABLISHED; 2], &[&placement(Some(1), 1), &placement(Some(1), 0)]);
+
+	assert!(after.iter().all(Option::is_none));
+}
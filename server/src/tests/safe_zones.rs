@@ -0,0 +1,39 @@
+use protocol::nalgebra::Point3;
+
+use crate::addon::pvp::safe_zones::Region;
+
+fn spawn() -> Region {
+	Region {
+		name: "spawn".to_owned(),
+		min: Point3::new(-10, -10, 0),
+		max: Point3::new(10, 10, 5)
+	}
+}
+
+#[test]
+fn contains_is_inclusive() {
+	let region = spawn();
+
+	assert!(region.contains(Point3::new(0, 0, 0)));
+	assert!(region.contains(Point3::new(-10, 10, 5)));
+	assert!(!region.contains(Point3::new(11, 0, 0)));
+	assert!(!region.contains(Point3::new(0, -11, 0)));
+	assert!(!region.contains(Point3::new(0, 0, 6)));
+}
+
+#[test]
+fn parse_reverses_serialize() {
+	let region = Region::parse(&spawn().serialize()).unwrap();
+
+	assert_eq!(region.name, "spawn");
+	assert_eq!(region.min, Point3::new(-10, -10, 0));
+	assert_eq!(region.max, Point3::new(10, 10, 5));
+}
+
+#[test]
+fn parse_rejects_malformed_lines() {
+	assert!(Region::parse("spawn;0;0;0;1;1").is_none());
+	assert!(Region::parse("spawn;0;0;0;1;1;1;1").is_none());
+	assert!(Region::parse("spawn;0;0;zero;1;1;1").is_none());
+	assert!(Region::parse("").is_none());
+}
@@ -0,0 +1,81 @@
+use protocol::nalgebra::Point3;
+use protocol::packet::common::CreatureId;
+use protocol::utils::constants::{SIZE_BLOCK, SIZE_ZONE};
+
+use crate::server::spatial_index::SpatialIndex;
+
+const RADIUS: i64 = SIZE_BLOCK * 100;
+
+fn at(x: i64, y: i64) -> Point3<i64> {
+	Point3::new(SIZE_ZONE * 1000 + x, SIZE_ZONE * 1000 + y, 0)
+}
+
+#[test]
+fn entering_range_is_symmetric() {
+	let mut index = SpatialIndex::new(RADIUS);
+	let first_changes = index.update(CreatureId(1), at(0, 0));
+	assert!(first_changes.entered.is_empty());
+
+	let second_changes = index.update(CreatureId(2), at(RADIUS, 0));
+	assert_eq!(second_changes.entered, vec![CreatureId(1)]);
+	assert!(index.in_range_of(CreatureId(1)).contains(&CreatureId(2)));
+	assert!(index.in_range_of(CreatureId(2)).contains(&CreatureId(1)));
+}
+
+#[test]
+fn range_is_circular() {
+	let mut index = SpatialIndex::new(RADIUS);
+	index.update(CreatureId(1), at(0, 0));
+
+	let diagonal = SIZE_BLOCK * 75; //106 blocks away
+	let changes = index.update(CreatureId(2), at(diagonal, diagonal));
+	assert!(changes.entered.is_empty());
+}
+
+#[test]
+fn range_spans_zone_borders() {
+	let mut index = SpatialIndex::new(SIZE_ZONE * 2);
+	index.update(CreatureId(1), at(-1, 0));
+
+	let changes = index.update(CreatureId(2), at(SIZE_ZONE * 2 - 1, 0));
+	assert_eq!(changes.entered, vec![CreatureId(1)]);
+}
+
+#[test]
+fn leaving_range_is_reported_once() {
+	let mut index = SpatialIndex::new(RADIUS);
+	index.update(CreatureId(1), at(0, 0));
+	index.update(CreatureId(2), at(0, 0));
+
+	let departure = index.update(CreatureId(2), at(RADIUS * 2, 0));
+	assert_eq!(departure.left, vec![CreatureId(1)]);
+	assert!(index.in_range_of(CreatureId(1)).is_empty());
+
+	let further_away = index.update(CreatureId(2), at(RADIUS * 3, 0));
+	assert!(further_away.left.is_empty());
+}
+
+#[test]
+fn removed_creatures_are_forgotten() {
+	let mut index = SpatialIndex::new(RADIUS);
+	index.update(CreatureId(1), at(0, 0));
+	index.update(CreatureId(2), at(0, 0));
+
+	index.remove(CreatureId(2));
+	assert!(!index.contains(CreatureId(2)));
+	assert!(index.in_range_of(CreatureId(1)).is_empty());
+
+	let changes = index.update(CreatureId(3), at(0, 0));
+	assert_eq!(changes.entered, vec![CreatureId(1)]);
+}
+
+#[test]
+fn forgotten_neighbours_enter_again() {
+	let mut index = SpatialIndex::new(RADIUS);
+	index.update(CreatureId(1), at(0, 0));
+	index.update(CreatureId(2), at(0, 0));
+
+	index.forget_neighbours(CreatureId(2));
+	let changes = index.update(CreatureId(2), at(0, 0));
+	assert_eq!(changes.entered, vec![CreatureId(1)]);
+}
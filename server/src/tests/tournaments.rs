@@ -0,0 +1,74 @@
+use crate::addon::pvp::tournaments::{Entrant, Format, Tournament};
+
+fn tournament(format: Format, losses: &[u32]) -> Tournament {
+	let entrants = losses
+		.iter()
+		.enumerate()
+		.map(|(seed, &losses)| Entrant {
+			name: seed.to_string(),
+			seed,
+			losses
+		})
+		.collect();
+
+	Tournament { format, entrants, round: 0, pairings: vec![], byes: vec![] }
+}
+
+fn pairings_of(tournament: &Tournament) -> Vec<[&str; 2]> {
+	tournament.pairings
+		.iter()
+		.map(|pairing| [pairing.names[0].as_str(), pairing.names[1].as_str()])
+		.collect()
+}
+
+#[test]
+fn best_seed_meets_worst_seed() {
+	let mut tournament = tournament(Format::SingleElimination, &[0, 0, 0, 0]);
+	tournament.next_round();
+
+	assert_eq!(tournament.round, 1);
+	assert_eq!(pairings_of(&tournament), [["0", "3"], ["1", "2"]]);
+	assert!(tournament.byes.is_empty());
+}
+
+#[test]
+fn odd_entrant_gets_a_bye() {
+	let mut tournament = tournament(Format::SingleElimination, &[0, 0, 0, 0, 0]);
+	tournament.next_round();
+
+	assert_eq!(pairings_of(&tournament), [["0", "4"], ["1", "3"]]);
+	assert_eq!(tournament.byes, ["2"]);
+}
+
+#[test]
+fn eliminated_entrants_are_skipped() {
+	let mut tournament = tournament(Format::SingleElimination, &[1, 0, 1, 0]);
+	tournament.next_round();
+
+	assert_eq!(pairings_of(&tournament), [["1", "3"]]);
+}
+
+#[test]
+fn double_elimination_pairs_within_brackets() {
+	let mut tournament = tournament(Format::DoubleElimination, &[0, 1, 0, 1, 2]);
+	tournament.next_round();
+
+	assert_eq!(pairings_of(&tournament), [["0", "2"], ["1", "3"]]);
+}
+
+#[test]
+fn last_two_meet_regardless_of_losses() {
+	let mut tournament = tournament(Format::DoubleElimination, &[0, 2, 1]);
+	tournament.next_round();
+
+	assert_eq!(pairings_of(&tournament), [["0", "2"]]);
+}
+
+#[test]
+fn lone_survivor_ends_the_tournament() {
+	let mut tournament = tournament(Format::SingleElimination, &[1, 0, 1]);
+	tournament.next_round();
+
+	assert!(tournament.pairings.is_empty());
+	assert!(tournament.byes.is_empty());
+}
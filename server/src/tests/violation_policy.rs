@@ -0,0 +1,49 @@
+use crate::addon::anti_cheat::violation::Action::*;
+use crate::addon::anti_cheat::violation::Policy;
+
+fn policy(value: &str) -> Policy {
+	value.parse().unwrap()
+}
+
+#[test]
+fn escalates_at_thresholds() {
+	let policy = policy("1;0:log_only,10:notify_admins,25:kick,50:temp_ban");
+
+	assert_eq!(policy.action_at(0.0), LogOnly);
+	assert_eq!(policy.action_at(9.9), LogOnly);
+	assert_eq!(policy.action_at(10.0), NotifyAdmins);
+	assert_eq!(policy.action_at(24.9), NotifyAdmins);
+	assert_eq!(policy.action_at(25.0), Kick);
+	assert_eq!(policy.action_at(1000.0), TempBan);
+}
+
+#[test]
+fn defaults_to_log_only_below_first_threshold() {
+	let policy = policy("5;3:kick");
+
+	assert_eq!(policy.action_at(0.0), LogOnly);
+	assert_eq!(policy.action_at(3.0), Kick);
+}
+
+#[test]
+fn parses_severity_and_sorts_escalation() {
+	let policy = policy("2.5; 20:kick, 0:rubber_band");
+
+	assert!((policy.severity - 2.5).abs() < f32::EPSILON);
+	assert_eq!(policy.escalation, vec![(0.0, RubberBand), (20.0, Kick)]);
+}
+
+#[test]
+fn empty_escalation_only_logs() {
+	let policy = policy("1;");
+
+	assert!(policy.escalation.is_empty());
+	assert_eq!(policy.action_at(100.0), LogOnly);
+}
+
+#[test]
+fn rejects_malformed_policies() {
+	for value in ["1", "x;0:kick", "1;0-kick", "1;0:explode", "1;zero:kick"] {
+		value.parse::<Policy>().unwrap_err();
+	}
+}